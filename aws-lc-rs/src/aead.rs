@@ -73,6 +73,7 @@ use std::mem::MaybeUninit;
 use std::ops::RangeFrom;
 
 mod aead_ctx;
+mod aes_ccm;
 mod aes_gcm;
 mod chacha;
 pub mod chacha20_poly1305_openssh;
@@ -80,11 +81,16 @@ mod nonce;
 pub mod nonce_sequence;
 mod poly1305;
 pub mod quic;
+mod rand_nonce;
+mod tls;
 
 pub use self::{
-    aes_gcm::{AES_128_GCM, AES_256_GCM},
-    chacha::CHACHA20_POLY1305,
-    nonce::{Nonce, NONCE_LEN},
+    aes_ccm::{AES_128_CCM, AES_128_CCM_4, AES_128_CCM_8},
+    aes_gcm::{AES_128_GCM, AES_128_GCM_SIV, AES_256_GCM, AES_256_GCM_SIV},
+    chacha::{CHACHA20_POLY1305, XCHACHA20_POLY1305},
+    nonce::{Nonce, CCM_NONCE_LEN, MAX_NONCE_LEN, NONCE_LEN},
+    rand_nonce::RandomizedNonceKey,
+    tls::{TlsProtocolId, TlsRecordOpeningKey, TlsRecordSealingKey},
 };
 
 /// A sequences of unique nonces.
@@ -259,12 +265,12 @@ fn open_within_<'in_out, A: AsRef<[u8]>>(
         let in_prefix_len = ciphertext_and_tag.start;
         let ciphertext_and_tag_len = in_out.len().checked_sub(in_prefix_len).ok_or(Unspecified)?;
         let ciphertext_len = ciphertext_and_tag_len
-            .checked_sub(TAG_LEN)
+            .checked_sub(key.algorithm.tag_len())
             .ok_or(Unspecified)?;
         check_per_nonce_max_bytes(key.algorithm, ciphertext_len)?;
-        let key_inner_ref = key.get_inner_key();
+        check_nonce_len(key.algorithm, &nonce)?;
 
-        aead_open_combined(key_inner_ref, nonce, aad, &mut in_out[in_prefix_len..])?;
+        aead_open_combined(key, nonce, aad, &mut in_out[in_prefix_len..])?;
 
         // shift the plaintext to the left
         in_out.copy_within(in_prefix_len..in_prefix_len + ciphertext_len, 0);
@@ -409,8 +415,8 @@ where
     InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
 {
     check_per_nonce_max_bytes(key.algorithm, in_out.as_mut().len())?;
-    let key_inner_ref = key.get_inner_key();
-    aead_seal_combined(key_inner_ref, nonce, aad, in_out)
+    check_nonce_len(key.algorithm, &nonce)?;
+    aead_seal_combined(key, nonce, aad, in_out)
 }
 
 #[inline]
@@ -421,8 +427,8 @@ fn seal_in_place_separate_tag_(
     in_out: &mut [u8],
 ) -> Result<Tag, Unspecified> {
     check_per_nonce_max_bytes(key.algorithm, in_out.len())?;
-    let key_inner_ref = key.get_inner_key();
-    aead_seal_separate(key_inner_ref, nonce, aad, in_out)
+    check_nonce_len(key.algorithm, &nonce)?;
+    aead_seal_separate(key, nonce, aad, in_out)
 }
 
 /// The additionally authenticated data (AAD) for an opening or sealing
@@ -651,6 +657,8 @@ impl Debug for LessSafeKey {
 pub struct Algorithm {
     init: fn(key: &[u8]) -> Result<AeadCtx, Unspecified>,
     key_len: usize,
+    tag_len: usize,
+    nonce_len: usize,
     id: AlgorithmID,
 
     // /// Use `max_input_len!()` to initialize this.
@@ -672,14 +680,14 @@ impl Algorithm {
     #[inline]
     #[must_use]
     pub fn tag_len(&self) -> usize {
-        TAG_LEN
+        self.tag_len
     }
 
     /// The length of the nonces.
     #[inline]
     #[must_use]
     pub fn nonce_len(&self) -> usize {
-        NONCE_LEN
+        self.nonce_len
     }
 }
 
@@ -690,7 +698,13 @@ derive_debug_via_id!(Algorithm);
 enum AlgorithmID {
     AES_128_GCM,
     AES_256_GCM,
+    AES_128_GCM_SIV,
+    AES_256_GCM_SIV,
+    AES_128_CCM,
+    AES_128_CCM_8,
+    AES_128_CCM_4,
     CHACHA20_POLY1305,
+    XCHACHA20_POLY1305,
 }
 
 impl PartialEq for Algorithm {
@@ -705,18 +719,19 @@ impl Eq for Algorithm {}
 /// An authentication tag.
 #[must_use]
 #[repr(C)]
-pub struct Tag([u8; TAG_LEN]);
+pub struct Tag([u8; MAX_TAG_LEN], usize);
 
 impl AsRef<[u8]> for Tag {
     fn as_ref(&self) -> &[u8] {
-        self.0.as_ref()
+        &self.0[..self.1]
     }
 }
 
 #[allow(dead_code)]
 const MAX_KEY_LEN: usize = 32;
 
-// All the AEADs we support use 128-bit tags.
+// The AEADs we support use 128-bit tags, except for the AES-CCM variants with
+// truncated tags.
 const TAG_LEN: usize = 16;
 
 /// The maximum length of a tag for the algorithms in this module.
@@ -736,10 +751,18 @@ fn check_per_nonce_max_bytes(alg: &Algorithm, in_out_len: usize) -> Result<(), U
     Ok(())
 }
 
+#[inline]
+fn check_nonce_len(alg: &Algorithm, nonce: &Nonce) -> Result<(), Unspecified> {
+    if nonce.as_ref().len() != alg.nonce_len {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
 #[inline]
 #[allow(clippy::needless_pass_by_value)]
 pub(crate) fn aead_seal_combined<InOut>(
-    key: &AeadCtx,
+    key: &UnboundKey,
     nonce: Nonce,
     aad: Aad<&[u8]>,
     in_out: &mut InOut,
@@ -748,16 +771,29 @@ where
     InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
 {
     unsafe {
-        let aead_ctx = match key {
+        let tag_len = key.algorithm.tag_len();
+        let aead_ctx = match key.get_inner_key() {
             AeadCtx::AES_128_GCM(aead_ctx)
             | AeadCtx::AES_256_GCM(aead_ctx)
-            | AeadCtx::CHACHA20_POLY1305(aead_ctx) => aead_ctx,
+            | AeadCtx::AES_128_GCM_SIV(aead_ctx)
+            | AeadCtx::AES_256_GCM_SIV(aead_ctx)
+            | AeadCtx::AES_128_GCM_RANDNONCE(aead_ctx)
+            | AeadCtx::AES_256_GCM_RANDNONCE(aead_ctx)
+            | AeadCtx::AES_128_GCM_TLS12(aead_ctx)
+            | AeadCtx::AES_256_GCM_TLS12(aead_ctx)
+            | AeadCtx::AES_128_GCM_TLS13(aead_ctx)
+            | AeadCtx::AES_256_GCM_TLS13(aead_ctx)
+            | AeadCtx::AES_128_CCM(aead_ctx)
+            | AeadCtx::AES_128_CCM_8(aead_ctx)
+            | AeadCtx::AES_128_CCM_4(aead_ctx)
+            | AeadCtx::CHACHA20_POLY1305(aead_ctx)
+            | AeadCtx::XCHACHA20_POLY1305(aead_ctx) => aead_ctx,
         };
         let nonce = nonce.as_ref();
 
         let plaintext_len = in_out.as_mut().len();
 
-        in_out.extend([0u8; TAG_LEN].iter());
+        in_out.extend([0u8; MAX_TAG_LEN][..tag_len].iter());
 
         let mut out_len = MaybeUninit::<usize>::uninit();
         let mut_in_out = in_out.as_mut();
@@ -767,9 +803,9 @@ where
             aead_ctx,
             mut_in_out.as_mut_ptr(),
             out_len.as_mut_ptr(),
-            plaintext_len + TAG_LEN,
+            plaintext_len + tag_len,
             nonce.as_ptr(),
-            NONCE_LEN,
+            nonce.len(),
             mut_in_out.as_ptr(),
             plaintext_len,
             add_str.as_ptr(),
@@ -785,20 +821,33 @@ where
 #[inline]
 #[allow(clippy::needless_pass_by_value)]
 pub(crate) fn aead_open_combined(
-    key: &AeadCtx,
+    key: &UnboundKey,
     nonce: Nonce,
     aad: Aad<&[u8]>,
     in_out: &mut [u8],
 ) -> Result<(), Unspecified> {
     unsafe {
-        let aead_ctx = match key {
+        let tag_len = key.algorithm.tag_len();
+        let aead_ctx = match key.get_inner_key() {
             AeadCtx::AES_128_GCM(aead_ctx)
             | AeadCtx::AES_256_GCM(aead_ctx)
-            | AeadCtx::CHACHA20_POLY1305(aead_ctx) => aead_ctx,
+            | AeadCtx::AES_128_GCM_SIV(aead_ctx)
+            | AeadCtx::AES_256_GCM_SIV(aead_ctx)
+            | AeadCtx::AES_128_GCM_RANDNONCE(aead_ctx)
+            | AeadCtx::AES_256_GCM_RANDNONCE(aead_ctx)
+            | AeadCtx::AES_128_GCM_TLS12(aead_ctx)
+            | AeadCtx::AES_256_GCM_TLS12(aead_ctx)
+            | AeadCtx::AES_128_GCM_TLS13(aead_ctx)
+            | AeadCtx::AES_256_GCM_TLS13(aead_ctx)
+            | AeadCtx::AES_128_CCM(aead_ctx)
+            | AeadCtx::AES_128_CCM_8(aead_ctx)
+            | AeadCtx::AES_128_CCM_4(aead_ctx)
+            | AeadCtx::CHACHA20_POLY1305(aead_ctx)
+            | AeadCtx::XCHACHA20_POLY1305(aead_ctx) => aead_ctx,
         };
         let nonce = nonce.as_ref();
 
-        let plaintext_len = in_out.len() - TAG_LEN;
+        let plaintext_len = in_out.len() - tag_len;
 
         let aad_str = aad.0;
         let mut out_len = MaybeUninit::<usize>::uninit();
@@ -808,9 +857,9 @@ pub(crate) fn aead_open_combined(
             out_len.as_mut_ptr(),
             plaintext_len,
             nonce.as_ptr(),
-            NONCE_LEN,
+            nonce.len(),
             in_out.as_ptr(),
-            plaintext_len + TAG_LEN,
+            plaintext_len + tag_len,
             aad_str.as_ptr(),
             aad_str.len(),
         ) {
@@ -824,7 +873,7 @@ pub(crate) fn aead_open_combined(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{iv::FixedLength, test::from_hex};
+    use crate::test::from_hex;
 
     #[test]
     fn test_aes_128() {
@@ -845,14 +894,18 @@ mod tests {
         #[allow(deprecated)]
         less_safe_key
             // Test coverage for `seal_in_place`, which calls `seal_in_place_append_tag`.
-            .seal_in_place(Nonce(FixedLength::from(nonce)), Aad::empty(), &mut in_out)
+            .seal_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
             .unwrap();
 
         let mut in_out_clone = in_out.clone();
         let nonce: [u8; NONCE_LEN] = og_nonce.as_slice().try_into().unwrap();
         assert!(less_safe_key
             .open_in_place(
-                Nonce(FixedLength::from(nonce)),
+                Nonce::assume_unique_for_key(nonce),
                 Aad::from("test"),
                 &mut in_out_clone
             )
@@ -863,7 +916,7 @@ mod tests {
         nonce[0] = 0;
         assert!(less_safe_key
             .open_in_place(
-                Nonce(FixedLength::from(nonce)),
+                Nonce::assume_unique_for_key(nonce),
                 Aad::empty(),
                 &mut in_out_clone
             )
@@ -871,7 +924,11 @@ mod tests {
 
         let nonce: [u8; NONCE_LEN] = og_nonce.as_slice().try_into().unwrap();
         less_safe_key
-            .open_in_place(Nonce(FixedLength::from(nonce)), Aad::empty(), &mut in_out)
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
             .unwrap();
 
         assert_eq!(plaintext, in_out[..plaintext.len()]);
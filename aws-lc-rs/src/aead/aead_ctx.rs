@@ -1,14 +1,18 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-use crate::aead::TAG_LEN;
+use crate::aead::{NONCE_LEN, TAG_LEN};
 use crate::cipher::chacha;
 
 use crate::cipher::aes::{AES_128_KEY_LEN, AES_256_KEY_LEN};
 use crate::error::Unspecified;
 use aws_lc::{
-    EVP_AEAD_CTX_cleanup, EVP_AEAD_CTX_init, EVP_AEAD_CTX_zero, EVP_aead_aes_128_gcm,
-    EVP_aead_aes_256_gcm, EVP_aead_chacha20_poly1305, EVP_AEAD_CTX,
+    EVP_AEAD_CTX_cleanup, EVP_AEAD_CTX_init, EVP_AEAD_CTX_zero, EVP_aead_aes_128_ccm_bluetooth,
+    EVP_aead_aes_128_ccm_bluetooth_8, EVP_aead_aes_128_ccm_matter, EVP_aead_aes_128_gcm,
+    EVP_aead_aes_128_gcm_randnonce, EVP_aead_aes_128_gcm_siv, EVP_aead_aes_128_gcm_tls12,
+    EVP_aead_aes_128_gcm_tls13, EVP_aead_aes_256_gcm, EVP_aead_aes_256_gcm_randnonce,
+    EVP_aead_aes_256_gcm_siv, EVP_aead_aes_256_gcm_tls12, EVP_aead_aes_256_gcm_tls13,
+    EVP_aead_chacha20_poly1305, EVP_aead_xchacha20_poly1305, EVP_AEAD_CTX,
 };
 use std::mem::MaybeUninit;
 use std::ptr::null_mut;
@@ -21,7 +25,19 @@ use std::ptr::null_mut;
 pub(crate) enum AeadCtx {
     AES_128_GCM(EVP_AEAD_CTX),
     AES_256_GCM(EVP_AEAD_CTX),
+    AES_128_GCM_SIV(EVP_AEAD_CTX),
+    AES_256_GCM_SIV(EVP_AEAD_CTX),
+    AES_128_GCM_RANDNONCE(EVP_AEAD_CTX),
+    AES_256_GCM_RANDNONCE(EVP_AEAD_CTX),
+    AES_128_GCM_TLS12(EVP_AEAD_CTX),
+    AES_256_GCM_TLS12(EVP_AEAD_CTX),
+    AES_128_GCM_TLS13(EVP_AEAD_CTX),
+    AES_256_GCM_TLS13(EVP_AEAD_CTX),
+    AES_128_CCM(EVP_AEAD_CTX),
+    AES_128_CCM_8(EVP_AEAD_CTX),
+    AES_128_CCM_4(EVP_AEAD_CTX),
     CHACHA20_POLY1305(EVP_AEAD_CTX),
+    XCHACHA20_POLY1305(EVP_AEAD_CTX),
 }
 
 unsafe impl Send for AeadCtx {}
@@ -35,6 +51,7 @@ impl AeadCtx {
         Ok(AeadCtx::AES_128_GCM(AeadCtx::build_context(
             EVP_aead_aes_128_gcm,
             key_bytes,
+            TAG_LEN,
         )?))
     }
 
@@ -45,9 +62,127 @@ impl AeadCtx {
         Ok(AeadCtx::AES_256_GCM(AeadCtx::build_context(
             EVP_aead_aes_256_gcm,
             key_bytes,
+            TAG_LEN,
         )?))
     }
 
+    pub(crate) fn aes_128_gcm_siv(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if AES_128_KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        Ok(AeadCtx::AES_128_GCM_SIV(AeadCtx::build_context(
+            EVP_aead_aes_128_gcm_siv,
+            key_bytes,
+            TAG_LEN,
+        )?))
+    }
+
+    pub(crate) fn aes_256_gcm_siv(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if AES_256_KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        Ok(AeadCtx::AES_256_GCM_SIV(AeadCtx::build_context(
+            EVP_aead_aes_256_gcm_siv,
+            key_bytes,
+            TAG_LEN,
+        )?))
+    }
+
+    // The randomized-nonce variants generate the nonce internally and emit it after the tag, so
+    // the tag length given to the context includes the nonce.
+    pub(crate) fn aes_128_gcm_randnonce(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if AES_128_KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        Ok(AeadCtx::AES_128_GCM_RANDNONCE(AeadCtx::build_context(
+            EVP_aead_aes_128_gcm_randnonce,
+            key_bytes,
+            TAG_LEN + NONCE_LEN,
+        )?))
+    }
+
+    pub(crate) fn aes_256_gcm_randnonce(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if AES_256_KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        Ok(AeadCtx::AES_256_GCM_RANDNONCE(AeadCtx::build_context(
+            EVP_aead_aes_256_gcm_randnonce,
+            key_bytes,
+            TAG_LEN + NONCE_LEN,
+        )?))
+    }
+
+    // The TLS variants track the last nonce used for sealing and reject any nonce whose counter
+    // portion does not increase. For TLS 1.3 the counter is recovered by XORing with the first
+    // nonce sealed under the key.
+    pub(crate) fn aes_128_gcm_tls12(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if AES_128_KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        Ok(AeadCtx::AES_128_GCM_TLS12(AeadCtx::build_context(
+            EVP_aead_aes_128_gcm_tls12,
+            key_bytes,
+            TAG_LEN,
+        )?))
+    }
+
+    pub(crate) fn aes_256_gcm_tls12(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if AES_256_KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        Ok(AeadCtx::AES_256_GCM_TLS12(AeadCtx::build_context(
+            EVP_aead_aes_256_gcm_tls12,
+            key_bytes,
+            TAG_LEN,
+        )?))
+    }
+
+    pub(crate) fn aes_128_gcm_tls13(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if AES_128_KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        Ok(AeadCtx::AES_128_GCM_TLS13(AeadCtx::build_context(
+            EVP_aead_aes_128_gcm_tls13,
+            key_bytes,
+            TAG_LEN,
+        )?))
+    }
+
+    pub(crate) fn aes_256_gcm_tls13(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if AES_256_KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        Ok(AeadCtx::AES_256_GCM_TLS13(AeadCtx::build_context(
+            EVP_aead_aes_256_gcm_tls13,
+            key_bytes,
+            TAG_LEN,
+        )?))
+    }
+
+    pub(crate) fn aes_128_ccm(key_bytes: &[u8], tag_len: usize) -> Result<Self, Unspecified> {
+        if AES_128_KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        match tag_len {
+            16 => Ok(AeadCtx::AES_128_CCM(AeadCtx::build_context(
+                EVP_aead_aes_128_ccm_matter,
+                key_bytes,
+                tag_len,
+            )?)),
+            8 => Ok(AeadCtx::AES_128_CCM_8(AeadCtx::build_context(
+                EVP_aead_aes_128_ccm_bluetooth_8,
+                key_bytes,
+                tag_len,
+            )?)),
+            4 => Ok(AeadCtx::AES_128_CCM_4(AeadCtx::build_context(
+                EVP_aead_aes_128_ccm_bluetooth,
+                key_bytes,
+                tag_len,
+            )?)),
+            _ => Err(Unspecified),
+        }
+    }
+
     pub(crate) fn chacha20(key_bytes: &[u8]) -> Result<Self, Unspecified> {
         if chacha::KEY_LEN != key_bytes.len() {
             return Err(Unspecified);
@@ -55,12 +190,25 @@ impl AeadCtx {
         Ok(AeadCtx::CHACHA20_POLY1305(AeadCtx::build_context(
             EVP_aead_chacha20_poly1305,
             key_bytes,
+            TAG_LEN,
+        )?))
+    }
+
+    pub(crate) fn xchacha20(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if chacha::KEY_LEN != key_bytes.len() {
+            return Err(Unspecified);
+        }
+        Ok(AeadCtx::XCHACHA20_POLY1305(AeadCtx::build_context(
+            EVP_aead_xchacha20_poly1305,
+            key_bytes,
+            TAG_LEN,
         )?))
     }
 
     fn build_context(
         aead_fn: unsafe extern "C" fn() -> *const aws_lc::evp_aead_st,
         key_bytes: &[u8],
+        tag_len: usize,
     ) -> Result<EVP_AEAD_CTX, Unspecified> {
         let mut aead_ctx = MaybeUninit::<EVP_AEAD_CTX>::uninit();
         unsafe {
@@ -71,7 +219,7 @@ impl AeadCtx {
                 aead,
                 key_bytes.as_ptr().cast(),
                 key_bytes.len(),
-                TAG_LEN,
+                tag_len,
                 null_mut(),
             ) {
                 return Err(Unspecified);
@@ -87,7 +235,19 @@ impl Drop for AeadCtx {
             let ctx = match self {
                 AeadCtx::AES_128_GCM(ctx)
                 | AeadCtx::AES_256_GCM(ctx)
-                | AeadCtx::CHACHA20_POLY1305(ctx) => ctx,
+                | AeadCtx::AES_128_GCM_SIV(ctx)
+                | AeadCtx::AES_256_GCM_SIV(ctx)
+                | AeadCtx::AES_128_GCM_RANDNONCE(ctx)
+                | AeadCtx::AES_256_GCM_RANDNONCE(ctx)
+                | AeadCtx::AES_128_GCM_TLS12(ctx)
+                | AeadCtx::AES_256_GCM_TLS12(ctx)
+                | AeadCtx::AES_128_GCM_TLS13(ctx)
+                | AeadCtx::AES_256_GCM_TLS13(ctx)
+                | AeadCtx::AES_128_CCM(ctx)
+                | AeadCtx::AES_128_CCM_8(ctx)
+                | AeadCtx::AES_128_CCM_4(ctx)
+                | AeadCtx::CHACHA20_POLY1305(ctx)
+                | AeadCtx::XCHACHA20_POLY1305(ctx) => ctx,
             };
             EVP_AEAD_CTX_cleanup(ctx);
             EVP_AEAD_CTX_zero(ctx);
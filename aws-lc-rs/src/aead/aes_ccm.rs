@@ -0,0 +1,70 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::aead::aead_ctx::AeadCtx;
+use crate::aead::{Algorithm, AlgorithmID, CCM_NONCE_LEN};
+use crate::cipher::aes::AES_128_KEY_LEN;
+use crate::error::Unspecified;
+
+// CCM with a 104-bit nonce leaves two bytes for the message length field, so a single
+// message can be at most 2^16 - 1 bytes long.
+const CCM_MAX_INPUT_LEN: u64 = (1 << 16) - 1;
+
+/// AES-128 in CCM mode with 128-bit tags and 104 bit nonces.
+///
+/// This is the configuration of CCM used by the [Matter] protocol. The length of a single
+/// message is limited to 2^16 - 1 bytes.
+///
+/// [Matter]: https://csa-iot.org/all-solutions/matter/
+pub static AES_128_CCM: Algorithm = Algorithm {
+    init: init_128_ccm,
+    key_len: AES_128_KEY_LEN,
+    tag_len: 16,
+    nonce_len: CCM_NONCE_LEN,
+    id: AlgorithmID::AES_128_CCM,
+    max_input_len: CCM_MAX_INPUT_LEN,
+};
+
+/// AES-128 in CCM mode with 64-bit tags and 104 bit nonces.
+///
+/// This is the configuration of CCM described in [RFC 3610] Section 8. The length of a single
+/// message is limited to 2^16 - 1 bytes.
+///
+/// [RFC 3610]: https://www.rfc-editor.org/rfc/rfc3610
+pub static AES_128_CCM_8: Algorithm = Algorithm {
+    init: init_128_ccm_8,
+    key_len: AES_128_KEY_LEN,
+    tag_len: 8,
+    nonce_len: CCM_NONCE_LEN,
+    id: AlgorithmID::AES_128_CCM_8,
+    max_input_len: CCM_MAX_INPUT_LEN,
+};
+
+/// AES-128 in CCM mode with 32-bit tags and 104 bit nonces.
+///
+/// This is the configuration of CCM used by Bluetooth Low Energy link-layer encryption.
+/// The short tags offer limited protection against forgeries, so this should only be used
+/// when a protocol requires it. The length of a single message is limited to 2^16 - 1 bytes.
+pub static AES_128_CCM_4: Algorithm = Algorithm {
+    init: init_128_ccm_4,
+    key_len: AES_128_KEY_LEN,
+    tag_len: 4,
+    nonce_len: CCM_NONCE_LEN,
+    id: AlgorithmID::AES_128_CCM_4,
+    max_input_len: CCM_MAX_INPUT_LEN,
+};
+
+#[inline]
+fn init_128_ccm(key: &[u8]) -> Result<AeadCtx, Unspecified> {
+    AeadCtx::aes_128_ccm(key, AES_128_CCM.tag_len)
+}
+
+#[inline]
+fn init_128_ccm_8(key: &[u8]) -> Result<AeadCtx, Unspecified> {
+    AeadCtx::aes_128_ccm(key, AES_128_CCM_8.tag_len)
+}
+
+#[inline]
+fn init_128_ccm_4(key: &[u8]) -> Result<AeadCtx, Unspecified> {
+    AeadCtx::aes_128_ccm(key, AES_128_CCM_4.tag_len)
+}
@@ -1,8 +1,9 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-use crate::aead::{Aad, Algorithm, AlgorithmID, Nonce, Tag, MAX_TAG_LEN};
-use std::mem::MaybeUninit;
+use crate::aead::{
+    Aad, Algorithm, AlgorithmID, Nonce, Tag, UnboundKey, MAX_TAG_LEN, NONCE_LEN, TAG_LEN,
+};
 
 use crate::aead::aead_ctx::AeadCtx;
 use crate::cipher::aes::{AES_128_KEY_LEN, AES_256_KEY_LEN};
@@ -13,28 +14,41 @@ use std::ptr::null;
 #[inline]
 #[allow(clippy::needless_pass_by_value)]
 pub(crate) fn aead_seal_separate(
-    key: &AeadCtx,
+    key: &UnboundKey,
     nonce: Nonce,
     aad: Aad<&[u8]>,
     in_out: &mut [u8],
 ) -> Result<Tag, Unspecified> {
     unsafe {
-        let aead_ctx = match key {
+        let aead_ctx = match key.get_inner_key() {
             AeadCtx::CHACHA20_POLY1305(aead_ctx)
             | AeadCtx::AES_128_GCM(aead_ctx)
-            | AeadCtx::AES_256_GCM(aead_ctx) => aead_ctx,
+            | AeadCtx::AES_256_GCM(aead_ctx)
+            | AeadCtx::AES_128_GCM_SIV(aead_ctx)
+            | AeadCtx::AES_256_GCM_SIV(aead_ctx)
+            | AeadCtx::AES_128_GCM_RANDNONCE(aead_ctx)
+            | AeadCtx::AES_256_GCM_RANDNONCE(aead_ctx)
+            | AeadCtx::AES_128_GCM_TLS12(aead_ctx)
+            | AeadCtx::AES_256_GCM_TLS12(aead_ctx)
+            | AeadCtx::AES_128_GCM_TLS13(aead_ctx)
+            | AeadCtx::AES_256_GCM_TLS13(aead_ctx)
+            | AeadCtx::AES_128_CCM(aead_ctx)
+            | AeadCtx::AES_128_CCM_8(aead_ctx)
+            | AeadCtx::AES_128_CCM_4(aead_ctx)
+            | AeadCtx::XCHACHA20_POLY1305(aead_ctx) => aead_ctx,
         };
 
         let aad_slice = aad.as_ref();
         let nonce = nonce.as_ref();
-        let mut tag = MaybeUninit::<[u8; MAX_TAG_LEN]>::uninit();
-        let mut out_tag_len = MaybeUninit::<usize>::uninit();
+        // Algorithms with short tags write fewer than `MAX_TAG_LEN` bytes.
+        let mut tag = [0u8; MAX_TAG_LEN];
+        let mut out_tag_len = 0usize;
 
         if 1 != EVP_AEAD_CTX_seal_scatter(
             aead_ctx,
             in_out.as_mut_ptr(),
-            tag.as_mut_ptr().cast(),
-            out_tag_len.as_mut_ptr(),
+            tag.as_mut_ptr(),
+            &mut out_tag_len,
             MAX_TAG_LEN,
             nonce.as_ptr(),
             nonce.len(),
@@ -47,7 +61,7 @@ pub(crate) fn aead_seal_separate(
         ) {
             return Err(Unspecified);
         }
-        Ok(Tag(tag.assume_init()))
+        Ok(Tag(tag, out_tag_len))
     }
 }
 
@@ -55,6 +69,8 @@ pub(crate) fn aead_seal_separate(
 pub static AES_128_GCM: Algorithm = Algorithm {
     init: init_128_aead,
     key_len: AES_128_KEY_LEN,
+    tag_len: TAG_LEN,
+    nonce_len: NONCE_LEN,
     id: AlgorithmID::AES_128_GCM,
     max_input_len: u64::MAX,
 };
@@ -63,10 +79,42 @@ pub static AES_128_GCM: Algorithm = Algorithm {
 pub static AES_256_GCM: Algorithm = Algorithm {
     init: init_256_aead,
     key_len: AES_256_KEY_LEN,
+    tag_len: TAG_LEN,
+    nonce_len: NONCE_LEN,
     id: AlgorithmID::AES_256_GCM,
     max_input_len: u64::MAX,
 };
 
+/// AES-128 in GCM-SIV mode with 128-bit tags and 96 bit nonces.
+///
+/// GCM-SIV is nonce-misuse resistant: repeating a nonce only reveals whether the same
+/// plaintext was sealed twice under that nonce. See [RFC 8452].
+///
+/// [RFC 8452]: https://www.rfc-editor.org/rfc/rfc8452
+pub static AES_128_GCM_SIV: Algorithm = Algorithm {
+    init: init_128_aead_siv,
+    key_len: AES_128_KEY_LEN,
+    tag_len: TAG_LEN,
+    nonce_len: NONCE_LEN,
+    id: AlgorithmID::AES_128_GCM_SIV,
+    max_input_len: u64::MAX,
+};
+
+/// AES-256 in GCM-SIV mode with 128-bit tags and 96 bit nonces.
+///
+/// GCM-SIV is nonce-misuse resistant: repeating a nonce only reveals whether the same
+/// plaintext was sealed twice under that nonce. See [RFC 8452].
+///
+/// [RFC 8452]: https://www.rfc-editor.org/rfc/rfc8452
+pub static AES_256_GCM_SIV: Algorithm = Algorithm {
+    init: init_256_aead_siv,
+    key_len: AES_256_KEY_LEN,
+    tag_len: TAG_LEN,
+    nonce_len: NONCE_LEN,
+    id: AlgorithmID::AES_256_GCM_SIV,
+    max_input_len: u64::MAX,
+};
+
 #[inline]
 fn init_128_aead(key: &[u8]) -> Result<AeadCtx, Unspecified> {
     AeadCtx::aes_128_gcm(key)
@@ -76,3 +124,13 @@ fn init_128_aead(key: &[u8]) -> Result<AeadCtx, Unspecified> {
 fn init_256_aead(key: &[u8]) -> Result<AeadCtx, Unspecified> {
     AeadCtx::aes_256_gcm(key)
 }
+
+#[inline]
+fn init_128_aead_siv(key: &[u8]) -> Result<AeadCtx, Unspecified> {
+    AeadCtx::aes_128_gcm_siv(key)
+}
+
+#[inline]
+fn init_256_aead_siv(key: &[u8]) -> Result<AeadCtx, Unspecified> {
+    AeadCtx::aes_256_gcm_siv(key)
+}
@@ -5,7 +5,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use crate::aead::aead_ctx::AeadCtx;
-use crate::aead::{Algorithm, AlgorithmID};
+use crate::aead::{Algorithm, AlgorithmID, MAX_NONCE_LEN, NONCE_LEN, TAG_LEN};
 use crate::cipher::chacha::KEY_LEN;
 use crate::error;
 
@@ -17,11 +17,33 @@ use crate::error;
 pub static CHACHA20_POLY1305: Algorithm = Algorithm {
     init: init_chacha_aead,
     key_len: KEY_LEN,
+    tag_len: TAG_LEN,
+    nonce_len: NONCE_LEN,
     id: AlgorithmID::CHACHA20_POLY1305,
     max_input_len: u64::MAX,
 };
 
+/// XChaCha20-Poly1305 as described in [draft-irtf-cfrg-xchacha].
+///
+/// The keys are 256 bits long and the nonces are 192 bits long. The longer
+/// nonces make it safe to choose them at random for the lifetime of a key.
+///
+/// [draft-irtf-cfrg-xchacha]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-xchacha-03
+pub static XCHACHA20_POLY1305: Algorithm = Algorithm {
+    init: init_xchacha_aead,
+    key_len: KEY_LEN,
+    tag_len: TAG_LEN,
+    nonce_len: MAX_NONCE_LEN,
+    id: AlgorithmID::XCHACHA20_POLY1305,
+    max_input_len: u64::MAX,
+};
+
 #[inline]
 fn init_chacha_aead(key: &[u8]) -> Result<AeadCtx, error::Unspecified> {
     AeadCtx::chacha20(key)
 }
+
+#[inline]
+fn init_xchacha_aead(key: &[u8]) -> Result<AeadCtx, error::Unspecified> {
+    AeadCtx::xchacha20(key)
+}
@@ -20,11 +20,11 @@
 //!    http://cvsweb.openbsd.org/cgi-bin/cvsweb/src/usr.bin/ssh/PROTOCOL.chacha20poly1305?annotate=HEAD
 //! [RFC 4253]: https://tools.ietf.org/html/rfc4253
 
-use super::{poly1305, Nonce, Tag};
+use super::{poly1305, Tag, NONCE_LEN};
 use crate::cipher::block::BLOCK_LEN;
 use crate::cipher::chacha::{self, ChaCha20Key};
-use crate::iv::FixedLength;
-use crate::{constant_time, endian::BigEndian, error};
+use crate::endian::{ArrayEncoding, BigEndian, Encoding};
+use crate::{constant_time, error};
 use core::convert::TryInto;
 
 /// A key for sealing packets.
@@ -56,19 +56,19 @@ impl SealingKey {
         tag_out: &mut [u8; TAG_LEN],
     ) {
         let nonce = make_nonce(sequence_number);
-        let poly_key = derive_poly1305_key(&self.key.k_2, Nonce(FixedLength::from(nonce.as_ref())));
+        let poly_key = derive_poly1305_key(&self.key.k_2, &nonce);
 
         {
             let (len_in_out, data_and_padding_in_out) =
                 plaintext_in_ciphertext_out.split_at_mut(PACKET_LENGTH_LEN);
 
-            self.key.k_1.encrypt_in_place(nonce.as_ref(), len_in_out, 0);
+            self.key.k_1.encrypt_in_place(&nonce, len_in_out, 0);
             self.key
                 .k_2
-                .encrypt_in_place(nonce.as_ref(), data_and_padding_in_out, 1);
+                .encrypt_in_place(&nonce, data_and_padding_in_out, 1);
         }
 
-        let Tag(tag) = poly1305::sign(poly_key, plaintext_in_ciphertext_out);
+        let Tag(tag, _) = poly1305::sign(poly_key, plaintext_in_ciphertext_out);
         tag_out.copy_from_slice(tag.as_ref());
     }
 }
@@ -100,9 +100,7 @@ impl OpeningKey {
     ) -> [u8; PACKET_LENGTH_LEN] {
         let mut packet_length = encrypted_packet_length;
         let nonce = make_nonce(sequence_number);
-        self.key
-            .k_1
-            .encrypt_in_place(nonce.as_ref(), &mut packet_length, 0);
+        self.key.k_1.encrypt_in_place(&nonce, &mut packet_length, 0);
         packet_length
     }
 
@@ -131,13 +129,13 @@ impl OpeningKey {
         // We must verify the tag before decrypting so that
         // `ciphertext_in_plaintext_out` is unmodified if verification fails.
         // This is beyond what we guarantee.
-        let poly_key = derive_poly1305_key(&self.key.k_2, Nonce(FixedLength::from(nonce.as_ref())));
+        let poly_key = derive_poly1305_key(&self.key.k_2, &nonce);
         verify(poly_key, ciphertext_in_plaintext_out, tag)?;
 
         let plaintext_in_ciphertext_out = &mut ciphertext_in_plaintext_out[PACKET_LENGTH_LEN..];
         self.key
             .k_2
-            .encrypt_in_place(nonce.as_ref(), plaintext_in_ciphertext_out, 1);
+            .encrypt_in_place(&nonce, plaintext_in_ciphertext_out, 1);
 
         Ok(plaintext_in_ciphertext_out)
     }
@@ -162,8 +160,13 @@ impl Key {
 }
 
 #[inline]
-fn make_nonce(sequence_number: u32) -> Nonce {
-    Nonce::from(BigEndian::from(sequence_number))
+fn make_nonce(sequence_number: u32) -> [u8; NONCE_LEN] {
+    let nonce = [
+        BigEndian::ZERO,
+        BigEndian::ZERO,
+        BigEndian::from(sequence_number),
+    ];
+    *nonce.as_byte_array()
 }
 
 /// The length of key.
@@ -177,15 +180,17 @@ pub const TAG_LEN: usize = BLOCK_LEN;
 
 #[inline]
 fn verify(key: poly1305::Key, msg: &[u8], tag: &[u8; TAG_LEN]) -> Result<(), error::Unspecified> {
-    let Tag(calculated_tag) = poly1305::sign(key, msg);
+    let Tag(calculated_tag, _) = poly1305::sign(key, msg);
     constant_time::verify_slices_are_equal(calculated_tag.as_ref(), tag)
 }
 
 #[inline]
-#[allow(clippy::needless_pass_by_value)]
-pub(super) fn derive_poly1305_key(chacha_key: &ChaCha20Key, nonce: Nonce) -> poly1305::Key {
+pub(super) fn derive_poly1305_key(
+    chacha_key: &ChaCha20Key,
+    nonce: &[u8; NONCE_LEN],
+) -> poly1305::Key {
     let mut key_bytes = [0u8; 2 * BLOCK_LEN];
-    chacha_key.encrypt_in_place(nonce.as_ref(), &mut key_bytes, 0);
+    chacha_key.encrypt_in_place(nonce, &mut key_bytes, 0);
     poly1305::Key::new(key_bytes)
 }
 
@@ -194,7 +199,7 @@ mod tests {
     use crate::aead::chacha20_poly1305_openssh::{
         derive_poly1305_key, OpeningKey, SealingKey, KEY_LEN, TAG_LEN,
     };
-    use crate::aead::Nonce;
+    use crate::aead::{Nonce, NONCE_LEN};
     use crate::cipher::chacha::ChaCha20Key;
     use crate::test;
 
@@ -210,7 +215,8 @@ mod tests {
         let chacha_key_bytes: [u8; 32] = <[u8; 32]>::try_from(chacha_key).unwrap();
         let chacha_key = ChaCha20Key::from(chacha_key_bytes);
         let iv = Nonce::from(&[45u32, 897, 4567]);
-        let poly1305_key = derive_poly1305_key(&chacha_key, iv);
+        let iv: [u8; NONCE_LEN] = iv.as_ref().try_into().unwrap();
+        let poly1305_key = derive_poly1305_key(&chacha_key, &iv);
 
         assert_eq!(&expected_poly1305_key, &poly1305_key.key_and_nonce);
     }
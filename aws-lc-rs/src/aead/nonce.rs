@@ -12,20 +12,36 @@ use std::mem::transmute_copy;
 ///
 /// The user must ensure, for a particular key, that each nonce is unique.
 ///
+/// A `Nonce` is either `NONCE_LEN` (96 bits), `CCM_NONCE_LEN` (104 bits) or `MAX_NONCE_LEN`
+/// (192 bits) bytes long. Its length must match the `nonce_len()` of the algorithm of the key it
+/// is used with.
+///
 /// `Nonce` intentionally doesn't implement `Clone` to ensure that each one is
 /// consumed at most once.
-pub struct Nonce(pub(crate) FixedLength<NONCE_LEN>);
+pub struct Nonce(NonceValue);
+
+enum NonceValue {
+    Nonce96(FixedLength<NONCE_LEN>),
+    Nonce104(FixedLength<CCM_NONCE_LEN>),
+    Nonce192(FixedLength<MAX_NONCE_LEN>),
+}
 
 impl Nonce {
     /// Constructs a `Nonce` with the given value, assuming that the value is
     /// unique for the lifetime of the key it is being used with.
     ///
-    /// Fails if `value` isn't `NONCE_LEN` bytes long.
+    /// Fails if `value` isn't `NONCE_LEN`, `CCM_NONCE_LEN` or `MAX_NONCE_LEN` bytes long.
     /// # Errors
-    /// `error::Unspecified` when byte slice length is not `NONCE_LEN`
+    /// `error::Unspecified` when byte slice length is not `NONCE_LEN`, `CCM_NONCE_LEN` or
+    /// `MAX_NONCE_LEN`
     #[inline]
     pub fn try_assume_unique_for_key(value: &[u8]) -> Result<Self, error::Unspecified> {
-        Ok(Self(FixedLength::<NONCE_LEN>::try_from(value)?))
+        match value.len() {
+            NONCE_LEN => Ok(Self(NonceValue::Nonce96(FixedLength::try_from(value)?))),
+            CCM_NONCE_LEN => Ok(Self(NonceValue::Nonce104(FixedLength::try_from(value)?))),
+            MAX_NONCE_LEN => Ok(Self(NonceValue::Nonce192(FixedLength::try_from(value)?))),
+            _ => Err(error::Unspecified),
+        }
     }
 
     /// Constructs a `Nonce` with the given value, assuming that the value is
@@ -33,21 +49,25 @@ impl Nonce {
     #[inline]
     #[must_use]
     pub fn assume_unique_for_key(value: [u8; NONCE_LEN]) -> Self {
-        Self(FixedLength::<NONCE_LEN>::from(value))
+        Self(NonceValue::Nonce96(FixedLength::from(value)))
     }
 }
 
-impl AsRef<[u8; NONCE_LEN]> for Nonce {
+impl AsRef<[u8]> for Nonce {
     #[inline]
-    fn as_ref(&self) -> &[u8; NONCE_LEN] {
-        self.0.as_ref()
+    fn as_ref(&self) -> &[u8] {
+        match &self.0 {
+            NonceValue::Nonce96(value) => value.as_ref(),
+            NonceValue::Nonce104(value) => value.as_ref(),
+            NonceValue::Nonce192(value) => value.as_ref(),
+        }
     }
 }
 
 impl From<&[u8; NONCE_LEN]> for Nonce {
     #[inline]
     fn from(bytes: &[u8; NONCE_LEN]) -> Self {
-        Self(FixedLength::from(bytes))
+        Self(NonceValue::Nonce96(FixedLength::from(bytes)))
     }
 }
 
@@ -56,7 +76,7 @@ impl From<&[u32; NONCE_LEN / 4]> for Nonce {
     fn from(values: &[u32; NONCE_LEN / 4]) -> Self {
         unsafe {
             let bytes: [u8; NONCE_LEN] = transmute_copy(values);
-            Nonce(FixedLength::from(bytes))
+            Nonce::assume_unique_for_key(bytes)
         }
     }
 }
@@ -65,7 +85,7 @@ impl From<BigEndian<u32>> for Nonce {
     #[inline]
     fn from(number: BigEndian<u32>) -> Self {
         let nonce = [BigEndian::ZERO, BigEndian::ZERO, number];
-        Nonce(FixedLength::from(*(nonce.as_byte_array())))
+        Nonce::assume_unique_for_key(*(nonce.as_byte_array()))
     }
 }
 
@@ -75,13 +95,22 @@ impl From<&[u8; IV_LEN]> for Nonce {
     fn from(bytes: &[u8; IV_LEN]) -> Self {
         let mut nonce_bytes = [0u8; NONCE_LEN];
         nonce_bytes.copy_from_slice(&bytes[0..NONCE_LEN]);
-        Nonce(FixedLength::from(nonce_bytes))
+        Nonce::assume_unique_for_key(nonce_bytes)
     }
 }
 
-/// All the AEADs we support use 96-bit nonces.
+/// The length of a 96-bit nonce, used by all the AEADs in this module except
+/// `XCHACHA20_POLY1305` and the AES-CCM algorithms.
 pub const NONCE_LEN: usize = 96 / 8;
 
+/// The length of a 104-bit nonce, used by the AES-CCM algorithms.
+pub const CCM_NONCE_LEN: usize = 104 / 8;
+
+/// The maximum length of a nonce for the algorithms in this module.
+///
+/// `XCHACHA20_POLY1305` uses 192-bit nonces.
+pub const MAX_NONCE_LEN: usize = 192 / 8;
+
 #[cfg(test)]
 mod tests {
 
@@ -94,4 +123,22 @@ mod tests {
 
         assert_eq!(&[1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], nonce.as_ref());
     }
+
+    #[test]
+    fn test_nonce_lengths() {
+        use crate::aead::{Nonce, CCM_NONCE_LEN, MAX_NONCE_LEN, NONCE_LEN};
+        let bytes = [0u8; MAX_NONCE_LEN + 1];
+
+        let nonce = Nonce::try_assume_unique_for_key(&bytes[..NONCE_LEN]).unwrap();
+        assert_eq!(NONCE_LEN, nonce.as_ref().len());
+
+        let nonce = Nonce::try_assume_unique_for_key(&bytes[..CCM_NONCE_LEN]).unwrap();
+        assert_eq!(CCM_NONCE_LEN, nonce.as_ref().len());
+
+        let nonce = Nonce::try_assume_unique_for_key(&bytes[..MAX_NONCE_LEN]).unwrap();
+        assert_eq!(MAX_NONCE_LEN, nonce.as_ref().len());
+
+        assert!(Nonce::try_assume_unique_for_key(&bytes[..16]).is_err());
+        assert!(Nonce::try_assume_unique_for_key(&bytes).is_err());
+    }
 }
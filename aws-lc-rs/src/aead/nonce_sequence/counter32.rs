@@ -3,7 +3,6 @@
 
 use crate::aead::{Nonce, NonceSequence, NONCE_LEN};
 use crate::error::Unspecified;
-use crate::iv::FixedLength;
 
 /// `Counter32` is an implementation of the `NonceSequence` trait.
 /// The internal state of a `Counter32` is a 32-bit unsigned counter that
@@ -119,7 +118,7 @@ impl NonceSequence for Counter32 {
         nonce_bytes[..8].copy_from_slice(&self.identifier);
         nonce_bytes[8..].copy_from_slice(&counter_bytes);
         self.counter = self.counter.wrapping_add(1);
-        Ok(Nonce(FixedLength::from(nonce_bytes)))
+        Ok(Nonce::assume_unique_for_key(nonce_bytes))
     }
 }
 
@@ -3,12 +3,14 @@
 
 use crate::aead::{Nonce, NonceSequence, NONCE_LEN};
 use crate::error::Unspecified;
-use crate::iv::FixedLength;
 
 /// `Counter64` is an implementation of the `NonceSequence` trait.
 /// The internal state of a `Counter64` is a 64-bit unsigned counter that
 /// increments on each call to `advance` and an optional 4-byte identifier. Counter and identifier
-/// values are used to construct each nonce.
+/// values are used to construct each nonce, which is then combined by XOR with an optional
+/// 12-byte IV.
+/// The IV allows a `Counter64` to produce the per-record nonces of TLS 1.3, see
+/// [Section 5.3 of RFC 8446](https://www.rfc-editor.org/rfc/rfc8446#section-5.3).
 /// A limit can be set on the number of nonces allowed to be generated, by default this limit is
 /// `u64::MAX`.
 /// See [Section 3.2 of RFC 5116](https://www.rfc-editor.org/rfc/rfc5116#section-3.2).
@@ -18,6 +20,7 @@ pub struct Counter64 {
     generated: u64,
     identifier: [u8; 4],
     counter: u64,
+    iv: [u8; NONCE_LEN],
 }
 
 /// `NonceSequenceBuilder` facilitates the building of a `Counter64`.
@@ -26,6 +29,7 @@ pub struct Counter64Builder {
     limit: u64,
     identifier: [u8; 4],
     counter: u64,
+    iv: [u8; NONCE_LEN],
 }
 
 impl Default for Counter64Builder {
@@ -42,6 +46,7 @@ impl Counter64Builder {
             limit: u64::MAX,
             identifier: [0u8; 4],
             counter: 0,
+            iv: [0u8; NONCE_LEN],
         }
     }
 
@@ -67,6 +72,16 @@ impl Counter64Builder {
         self
     }
 
+    /// The IV that is combined by XOR with each nonce produced by the `Counter64`.
+    ///
+    /// For TLS 1.3, leave the identifier unset and use the record sequence number as the
+    /// counter and the traffic IV as the IV.
+    #[must_use]
+    pub fn iv(mut self, iv: [u8; NONCE_LEN]) -> Counter64Builder {
+        self.iv = iv;
+        self
+    }
+
     /// Constructs a new `Counter64` with internal identifier and counter set to the
     /// values provided by this struct.
     #[must_use]
@@ -76,6 +91,7 @@ impl Counter64Builder {
             generated: 0,
             identifier: self.identifier,
             counter: self.counter,
+            iv: self.iv,
         }
     }
 }
@@ -104,6 +120,12 @@ impl Counter64 {
     pub fn limit(&self) -> u64 {
         self.limit
     }
+
+    /// Provides the IV that is combined by XOR with each nonce.
+    #[must_use]
+    pub fn iv(&self) -> [u8; NONCE_LEN] {
+        self.iv
+    }
 }
 
 impl NonceSequence for Counter64 {
@@ -116,8 +138,11 @@ impl NonceSequence for Counter64 {
         let mut nonce_bytes = [0u8; NONCE_LEN];
         nonce_bytes[..4].copy_from_slice(&self.identifier);
         nonce_bytes[4..].copy_from_slice(&bytes);
+        for (b, iv) in nonce_bytes.iter_mut().zip(self.iv.iter()) {
+            *b ^= iv;
+        }
         self.counter = self.counter.wrapping_add(1);
-        Ok(Nonce(FixedLength::from(nonce_bytes)))
+        Ok(Nonce::assume_unique_for_key(nonce_bytes))
     }
 }
 
@@ -175,6 +200,27 @@ mod tests {
         assert_eq!(nonce, &[0, 0, 0, 0x7B, 0, 0, 0, 0, 0, 0, 0, 0x6B]);
     }
 
+    #[test]
+    fn test_counter64_iv() {
+        let iv = [
+            0x5D, 0x31, 0x3E, 0xB2, 0x67, 0x1A, 0xEC, 0xC0, 0x48, 0xE6, 0xFF, 0xFE,
+        ];
+        let mut cns = Counter64Builder::new().iv(iv).build();
+        assert_eq!(iv, cns.iv());
+        let nonce = cns.advance().unwrap();
+        assert_eq!(nonce.as_ref(), &iv);
+        let nonce = cns.advance().unwrap();
+        assert_eq!(
+            nonce.as_ref(),
+            &[0x5D, 0x31, 0x3E, 0xB2, 0x67, 0x1A, 0xEC, 0xC0, 0x48, 0xE6, 0xFF, 0xFF]
+        );
+        let nonce = cns.advance().unwrap();
+        assert_eq!(
+            nonce.as_ref(),
+            &[0x5D, 0x31, 0x3E, 0xB2, 0x67, 0x1A, 0xEC, 0xC0, 0x48, 0xE6, 0xFF, 0xFC]
+        );
+    }
+
     #[test]
     fn test_counter64_limit() {
         let mut cns = Counter64Builder::new().limit(1).build();
@@ -168,6 +168,7 @@ fn cipher_new_mask(
                 encrypt_block_chacha20(raw_key, input, nonce, counter)?
             }
         }
+        SymmetricCipherKey::Aes256Xts { .. } => return Err(error::Unspecified),
     };
 
     let mut out: [u8; 5] = [0; 5];
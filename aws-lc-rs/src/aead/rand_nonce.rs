@@ -0,0 +1,264 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::aead::aead_ctx::AeadCtx;
+use crate::aead::{Aad, Algorithm, AlgorithmID, Nonce, Tag, MAX_TAG_LEN, NONCE_LEN, TAG_LEN};
+use crate::error::Unspecified;
+use aws_lc::{EVP_AEAD_CTX_open_gather, EVP_AEAD_CTX_seal_scatter, EVP_AEAD_CTX};
+use std::fmt::Debug;
+use std::mem::MaybeUninit;
+use std::ops::RangeFrom;
+use std::ptr::null;
+
+/// AEAD Cipher key using a randomized nonce.
+///
+/// `RandomizedNonceKey` handles generation of the nonce for each seal operation: a random 96-bit
+/// nonce is generated inside *AWS-LC* and returned to the caller alongside the ciphertext. The
+/// same nonce must then be given back to open the ciphertext.
+///
+/// Only `AES_128_GCM` and `AES_256_GCM` are supported. Since nonces are chosen at random, the
+/// number of messages sealed under a single key should be kept well below 2^32.
+pub struct RandomizedNonceKey {
+    ctx: AeadCtx,
+    algorithm: &'static Algorithm,
+}
+
+impl RandomizedNonceKey {
+    /// New Random Nonce AEAD Cipher Key
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `algorithm` is not `AES_128_GCM` or `AES_256_GCM`, or if
+    ///   `key` is not the correct length for the algorithm.
+    pub fn new(algorithm: &'static Algorithm, key: &[u8]) -> Result<Self, Unspecified> {
+        let ctx = match algorithm.id {
+            AlgorithmID::AES_128_GCM => AeadCtx::aes_128_gcm_randnonce(key),
+            AlgorithmID::AES_256_GCM => AeadCtx::aes_256_gcm_randnonce(key),
+            _ => Err(Unspecified),
+        }?;
+        Ok(Self { ctx, algorithm })
+    }
+
+    /// Authenticates and decrypts (“opens”) data in place.
+    ///
+    /// `nonce` must be the nonce returned when the data was sealed.
+    ///
+    /// See [`super::LessSafeKey::open_in_place`] for details.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when ciphertext is invalid.
+    #[inline]
+    pub fn open_in_place<'in_out, A>(
+        &self,
+        nonce: Nonce,
+        aad: Aad<A>,
+        in_out: &'in_out mut [u8],
+    ) -> Result<&'in_out mut [u8], Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        self.open_within(nonce, aad, in_out, 0..)
+    }
+
+    /// Authenticates and decrypts (“opens”) data in place, with a shift.
+    ///
+    /// `nonce` must be the nonce returned when the data was sealed.
+    ///
+    /// See [`super::LessSafeKey::open_within`] for details.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when ciphertext is invalid.
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn open_within<'in_out, A>(
+        &self,
+        nonce: Nonce,
+        aad: Aad<A>,
+        in_out: &'in_out mut [u8],
+        ciphertext_and_tag: RangeFrom<usize>,
+    ) -> Result<&'in_out mut [u8], Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        let nonce = nonce.as_ref();
+        if nonce.len() != NONCE_LEN {
+            return Err(Unspecified);
+        }
+        let in_prefix_len = ciphertext_and_tag.start;
+        let ciphertext_and_tag_len = in_out.len().checked_sub(in_prefix_len).ok_or(Unspecified)?;
+        let ciphertext_len = ciphertext_and_tag_len
+            .checked_sub(TAG_LEN)
+            .ok_or(Unspecified)?;
+
+        // The randomized-nonce construction expects the nonce to follow the tag.
+        let mut tag_and_nonce = [0u8; TAG_LEN + NONCE_LEN];
+        tag_and_nonce[..TAG_LEN].copy_from_slice(&in_out[in_prefix_len + ciphertext_len..]);
+        tag_and_nonce[TAG_LEN..].copy_from_slice(nonce);
+
+        let ciphertext = &mut in_out[in_prefix_len..in_prefix_len + ciphertext_len];
+        let aad = aad.as_ref();
+        if 1 != unsafe {
+            EVP_AEAD_CTX_open_gather(
+                self.get_ctx(),
+                ciphertext.as_mut_ptr(),
+                null(),
+                0,
+                ciphertext.as_ptr(),
+                ciphertext_len,
+                tag_and_nonce.as_ptr(),
+                tag_and_nonce.len(),
+                aad.as_ptr(),
+                aad.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+
+        // shift the plaintext to the left
+        in_out.copy_within(in_prefix_len..in_prefix_len + ciphertext_len, 0);
+
+        // `ciphertext_len` is also the plaintext length.
+        Ok(&mut in_out[..ciphertext_len])
+    }
+
+    /// Encrypts and signs (“seals”) data in place, appending the tag to the
+    /// resulting ciphertext.
+    ///
+    /// The randomly generated nonce used for the operation is returned, and must be provided
+    /// to [`Self::open_in_place`] to open the data.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if encryption operation fails.
+    #[inline]
+    pub fn seal_in_place_append_tag<A, InOut>(
+        &self,
+        aad: Aad<A>,
+        in_out: &mut InOut,
+    ) -> Result<Nonce, Unspecified>
+    where
+        A: AsRef<[u8]>,
+        InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
+    {
+        let (nonce, tag) = self.seal_in_place_separate_tag(aad, in_out.as_mut())?;
+        in_out.extend(tag.as_ref());
+        Ok(nonce)
+    }
+
+    /// Encrypts and signs (“seals”) data in place.
+    ///
+    /// The randomly generated nonce used for the operation is returned along with the tag, and
+    /// must be provided to [`Self::open_in_place`] to open the data.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if encryption operation fails.
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn seal_in_place_separate_tag<A>(
+        &self,
+        aad: Aad<A>,
+        in_out: &mut [u8],
+    ) -> Result<(Nonce, Tag), Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        let aad = aad.as_ref();
+        let mut tag_and_nonce = [0u8; TAG_LEN + NONCE_LEN];
+        let mut out_tag_len = MaybeUninit::<usize>::uninit();
+
+        if 1 != unsafe {
+            EVP_AEAD_CTX_seal_scatter(
+                self.get_ctx(),
+                in_out.as_mut_ptr(),
+                tag_and_nonce.as_mut_ptr(),
+                out_tag_len.as_mut_ptr(),
+                tag_and_nonce.len(),
+                null(),
+                0,
+                in_out.as_ptr(),
+                in_out.len(),
+                null(),
+                0,
+                aad.as_ptr(),
+                aad.len(),
+            )
+        } || unsafe { out_tag_len.assume_init() } != tag_and_nonce.len()
+        {
+            return Err(Unspecified);
+        }
+
+        let mut tag = [0u8; MAX_TAG_LEN];
+        tag[..TAG_LEN].copy_from_slice(&tag_and_nonce[..TAG_LEN]);
+        let nonce = Nonce::try_assume_unique_for_key(&tag_and_nonce[TAG_LEN..])?;
+
+        Ok((nonce, Tag(tag, TAG_LEN)))
+    }
+
+    /// The key's AEAD algorithm.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+
+    #[inline]
+    fn get_ctx(&self) -> &EVP_AEAD_CTX {
+        match &self.ctx {
+            AeadCtx::AES_128_GCM_RANDNONCE(ctx) | AeadCtx::AES_256_GCM_RANDNONCE(ctx) => ctx,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl Debug for RandomizedNonceKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        f.debug_struct("RandomizedNonceKey")
+            .field("algorithm", &self.algorithm)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aead::{Aad, RandomizedNonceKey, AES_128_GCM, AES_256_GCM, CHACHA20_POLY1305};
+    use crate::test;
+
+    #[test]
+    fn test_new() {
+        assert!(RandomizedNonceKey::new(&AES_128_GCM, &[0u8; 16]).is_ok());
+        assert!(RandomizedNonceKey::new(&AES_256_GCM, &[0u8; 32]).is_ok());
+        assert!(RandomizedNonceKey::new(&AES_128_GCM, &[0u8; 32]).is_err());
+        assert!(RandomizedNonceKey::new(&AES_256_GCM, &[0u8; 16]).is_err());
+        assert!(RandomizedNonceKey::new(&CHACHA20_POLY1305, &[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_debug() {
+        let key = RandomizedNonceKey::new(&AES_256_GCM, &[0u8; 32]).unwrap();
+        assert_eq!(
+            "RandomizedNonceKey { algorithm: AES_256_GCM }",
+            format!("{key:?}")
+        );
+        assert_eq!(&AES_256_GCM, key.algorithm());
+    }
+
+    #[test]
+    fn test_types() {
+        test::compile_time_assert_send::<RandomizedNonceKey>();
+        test::compile_time_assert_sync::<RandomizedNonceKey>();
+    }
+
+    #[test]
+    fn test_nonces_differ() {
+        let key = RandomizedNonceKey::new(&AES_128_GCM, &[0u8; 16]).unwrap();
+        let mut first = Vec::from(&b"same plaintext"[..]);
+        let mut second = first.clone();
+        let first_nonce = key
+            .seal_in_place_append_tag(Aad::empty(), &mut first)
+            .unwrap();
+        let second_nonce = key
+            .seal_in_place_append_tag(Aad::empty(), &mut second)
+            .unwrap();
+        assert_ne!(first_nonce.as_ref(), second_nonce.as_ref());
+        assert_ne!(first, second);
+    }
+}
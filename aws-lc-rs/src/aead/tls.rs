@@ -0,0 +1,400 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! TLS record-layer AEAD keys.
+//!
+//! The keys in this module seal records using the TLS-specific AES-GCM constructions provided by
+//! *AWS-LC*. Besides performing ordinary AES-GCM, these constructions check that the nonce
+//! produced for each record follows the counter-based construction required by the protocol,
+//! as described for each [`TlsProtocolId`]. A nonce that violates this construction causes
+//! sealing to fail, so a misbehaving `NonceSequence` cannot cause a nonce to be reused.
+
+use crate::aead::aead_ctx::AeadCtx;
+use crate::aead::{
+    Aad, Algorithm, AlgorithmID, BoundKey, NonceSequence, OpeningKey, SealingKey, Tag, UnboundKey,
+};
+use crate::error::Unspecified;
+use std::fmt::Debug;
+use std::ops::RangeFrom;
+
+/// The TLS protocol version for which a record-layer key is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TlsProtocolId {
+    /// TLS 1.2 ([RFC 5246](https://www.rfc-editor.org/rfc/rfc5246))
+    ///
+    /// The nonce is a 4-byte implicit salt followed by an 8-byte explicit counter
+    /// ([RFC 5288, Section 3](https://www.rfc-editor.org/rfc/rfc5288#section-3)). The counter
+    /// must strictly increase from one record to the next. A
+    /// [`Counter64`](crate::aead::nonce_sequence::Counter64) whose identifier is the salt and
+    /// whose counter is the record sequence number produces exactly these nonces.
+    TLS12,
+    /// TLS 1.3 ([RFC 8446](https://www.rfc-editor.org/rfc/rfc8446))
+    ///
+    /// The nonce is the 12-byte IV combined by exclusive-or with the 64-bit record sequence
+    /// number ([RFC 8446, Section 5.3](https://www.rfc-editor.org/rfc/rfc8446#section-5.3)).
+    /// The first nonce sealed under the key is taken to be for sequence number zero, and each
+    /// later nonce must correspond to a strictly greater sequence number. A
+    /// [`Counter64`](crate::aead::nonce_sequence::Counter64) built with the IV set by
+    /// [`Counter64Builder::iv`](crate::aead::nonce_sequence::Counter64Builder::iv) and no
+    /// identifier produces exactly these nonces.
+    TLS13,
+}
+
+/// An AEAD key for sealing TLS records, bound to a nonce sequence.
+///
+/// Sealing fails if the nonce sequence produces a nonce that does not follow the counter-based
+/// construction for the key's protocol version, as described for each [`TlsProtocolId`].
+///
+/// Intentionally not `Clone` or `Copy` since cloning would allow duplication
+/// of the nonce sequence.
+pub struct TlsRecordSealingKey<N: NonceSequence> {
+    key: SealingKey<N>,
+    protocol: TlsProtocolId,
+}
+
+impl<N: NonceSequence> TlsRecordSealingKey<N> {
+    /// Constructs a new TLS record sealing key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `algorithm` is not `AES_128_GCM` or `AES_256_GCM`, or if
+    /// `key_bytes` is not the correct length for the algorithm.
+    pub fn new(
+        algorithm: &'static Algorithm,
+        protocol: TlsProtocolId,
+        key_bytes: &[u8],
+        nonce_sequence: N,
+    ) -> Result<Self, Unspecified> {
+        let ctx = match (algorithm.id, protocol) {
+            (AlgorithmID::AES_128_GCM, TlsProtocolId::TLS12) => {
+                AeadCtx::aes_128_gcm_tls12(key_bytes)
+            }
+            (AlgorithmID::AES_256_GCM, TlsProtocolId::TLS12) => {
+                AeadCtx::aes_256_gcm_tls12(key_bytes)
+            }
+            (AlgorithmID::AES_128_GCM, TlsProtocolId::TLS13) => {
+                AeadCtx::aes_128_gcm_tls13(key_bytes)
+            }
+            (AlgorithmID::AES_256_GCM, TlsProtocolId::TLS13) => {
+                AeadCtx::aes_256_gcm_tls13(key_bytes)
+            }
+            _ => Err(Unspecified),
+        }?;
+        let key = UnboundKey {
+            inner: ctx,
+            algorithm,
+        };
+        Ok(Self {
+            key: SealingKey::new(key, nonce_sequence),
+            protocol,
+        })
+    }
+
+    /// Encrypts and signs (“seals”) a record in place, appending the tag to the
+    /// resulting ciphertext.
+    ///
+    /// See [`SealingKey::seal_in_place_append_tag`] for details.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when `nonce_sequence` cannot be advanced, or when the nonce
+    /// it produces is not valid for the next record.
+    #[inline]
+    pub fn seal_in_place_append_tag<A, InOut>(
+        &mut self,
+        aad: Aad<A>,
+        in_out: &mut InOut,
+    ) -> Result<(), Unspecified>
+    where
+        A: AsRef<[u8]>,
+        InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
+    {
+        self.key.seal_in_place_append_tag(aad, in_out)
+    }
+
+    /// Encrypts and signs (“seals”) a record in place.
+    ///
+    /// See [`SealingKey::seal_in_place_separate_tag`] for details.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when `nonce_sequence` cannot be advanced, or when the nonce
+    /// it produces is not valid for the next record.
+    #[inline]
+    pub fn seal_in_place_separate_tag<A>(
+        &mut self,
+        aad: Aad<A>,
+        in_out: &mut [u8],
+    ) -> Result<Tag, Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        self.key.seal_in_place_separate_tag(aad, in_out)
+    }
+
+    /// The key's AEAD algorithm.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.key.algorithm()
+    }
+
+    /// The TLS protocol version of the key.
+    #[inline]
+    #[must_use]
+    pub fn tls_protocol_id(&self) -> TlsProtocolId {
+        self.protocol
+    }
+}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl<N: NonceSequence> Debug for TlsRecordSealingKey<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        f.debug_struct("TlsRecordSealingKey")
+            .field("algorithm", &self.algorithm())
+            .field("protocol", &self.protocol)
+            .finish()
+    }
+}
+
+/// An AEAD key for opening TLS records, bound to a nonce sequence.
+///
+/// The nonce sequence must produce the same nonces as the peer's sealing key, following the
+/// construction described for each [`TlsProtocolId`].
+///
+/// Intentionally not `Clone` or `Copy` since cloning would allow duplication
+/// of the nonce sequence.
+pub struct TlsRecordOpeningKey<N: NonceSequence> {
+    key: OpeningKey<N>,
+    protocol: TlsProtocolId,
+}
+
+impl<N: NonceSequence> TlsRecordOpeningKey<N> {
+    /// Constructs a new TLS record opening key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `algorithm` is not `AES_128_GCM` or `AES_256_GCM`, or if
+    /// `key_bytes` is not the correct length for the algorithm.
+    pub fn new(
+        algorithm: &'static Algorithm,
+        protocol: TlsProtocolId,
+        key_bytes: &[u8],
+        nonce_sequence: N,
+    ) -> Result<Self, Unspecified> {
+        // Opening does not depend on previous records, so the plain AES-GCM construction is used.
+        let ctx = match algorithm.id {
+            AlgorithmID::AES_128_GCM => AeadCtx::aes_128_gcm(key_bytes),
+            AlgorithmID::AES_256_GCM => AeadCtx::aes_256_gcm(key_bytes),
+            _ => Err(Unspecified),
+        }?;
+        let key = UnboundKey {
+            inner: ctx,
+            algorithm,
+        };
+        Ok(Self {
+            key: OpeningKey::new(key, nonce_sequence),
+            protocol,
+        })
+    }
+
+    /// Authenticates and decrypts (“opens”) a record in place.
+    ///
+    /// See [`OpeningKey::open_in_place`] for details.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when `nonce_sequence` cannot be advanced or the ciphertext is
+    /// invalid. In this case, `in_out` may have been overwritten in an unspecified way.
+    #[inline]
+    pub fn open_in_place<'in_out, A>(
+        &mut self,
+        aad: Aad<A>,
+        in_out: &'in_out mut [u8],
+    ) -> Result<&'in_out mut [u8], Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        self.key.open_in_place(aad, in_out)
+    }
+
+    /// Authenticates and decrypts (“opens”) a record in place, with a shift.
+    ///
+    /// See [`OpeningKey::open_within`] for details.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when `nonce_sequence` cannot be advanced or the ciphertext is
+    /// invalid. In this case, `in_out` may have been overwritten in an unspecified way.
+    #[inline]
+    pub fn open_within<'in_out, A>(
+        &mut self,
+        aad: Aad<A>,
+        in_out: &'in_out mut [u8],
+        ciphertext_and_tag: RangeFrom<usize>,
+    ) -> Result<&'in_out mut [u8], Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        self.key.open_within(aad, in_out, ciphertext_and_tag)
+    }
+
+    /// The key's AEAD algorithm.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.key.algorithm()
+    }
+
+    /// The TLS protocol version of the key.
+    #[inline]
+    #[must_use]
+    pub fn tls_protocol_id(&self) -> TlsProtocolId {
+        self.protocol
+    }
+}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl<N: NonceSequence> Debug for TlsRecordOpeningKey<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        f.debug_struct("TlsRecordOpeningKey")
+            .field("algorithm", &self.algorithm())
+            .field("protocol", &self.protocol)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TlsProtocolId, TlsRecordOpeningKey, TlsRecordSealingKey};
+    use crate::aead::nonce_sequence::{Counter64, Counter64Builder};
+    use crate::aead::{
+        Aad, LessSafeKey, Nonce, NonceSequence, UnboundKey, AES_128_GCM, AES_256_GCM,
+        CHACHA20_POLY1305, NONCE_LEN,
+    };
+    use crate::error::Unspecified;
+
+    #[test]
+    fn test_new() {
+        for protocol in [TlsProtocolId::TLS12, TlsProtocolId::TLS13] {
+            assert!(TlsRecordSealingKey::new(
+                &AES_128_GCM,
+                protocol,
+                &[0u8; 16],
+                Counter64Builder::new().build()
+            )
+            .is_ok());
+            assert!(TlsRecordSealingKey::new(
+                &AES_256_GCM,
+                protocol,
+                &[0u8; 16],
+                Counter64Builder::new().build()
+            )
+            .is_err());
+            assert!(TlsRecordSealingKey::new(
+                &CHACHA20_POLY1305,
+                protocol,
+                &[0u8; 32],
+                Counter64Builder::new().build()
+            )
+            .is_err());
+            assert!(TlsRecordOpeningKey::new(
+                &CHACHA20_POLY1305,
+                protocol,
+                &[0u8; 32],
+                Counter64Builder::new().build()
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        let key: TlsRecordSealingKey<Counter64> = TlsRecordSealingKey::new(
+            &AES_256_GCM,
+            TlsProtocolId::TLS13,
+            &[0u8; 32],
+            Counter64Builder::new().build(),
+        )
+        .unwrap();
+        assert_eq!(
+            "TlsRecordSealingKey { algorithm: AES_256_GCM, protocol: TLS13 }",
+            format!("{key:?}")
+        );
+        assert_eq!(TlsProtocolId::TLS13, key.tls_protocol_id());
+        assert_eq!(&AES_256_GCM, key.algorithm());
+    }
+
+    #[test]
+    fn test_tls13_iv() {
+        const KEY: [u8; 16] = [
+            0x17, 0x42, 0x2D, 0xDA, 0x59, 0x6E, 0xD5, 0xD9, 0xAC, 0xD8, 0x90, 0xE3, 0xC6, 0x3F,
+            0x50, 0x51,
+        ];
+        const IV: [u8; NONCE_LEN] = [
+            0x5B, 0x78, 0x92, 0x3D, 0xEE, 0x08, 0x57, 0x90, 0x33, 0xE5, 0x23, 0xD9,
+        ];
+
+        let mut sealing_key = TlsRecordSealingKey::new(
+            &AES_128_GCM,
+            TlsProtocolId::TLS13,
+            &KEY,
+            Counter64Builder::new().iv(IV).build(),
+        )
+        .unwrap();
+        let mut opening_key = TlsRecordOpeningKey::new(
+            &AES_128_GCM,
+            TlsProtocolId::TLS13,
+            &KEY,
+            Counter64Builder::new().iv(IV).build(),
+        )
+        .unwrap();
+        let reference_key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &KEY).unwrap());
+
+        for sequence_number in 0u64..8 {
+            let record = sequence_number.to_be_bytes();
+            let mut in_out = Vec::from(&record[..]);
+            sealing_key
+                .seal_in_place_append_tag(Aad::empty(), &mut in_out)
+                .unwrap();
+
+            let mut nonce = IV;
+            for (n, s) in nonce[4..].iter_mut().zip(record.iter()) {
+                *n ^= s;
+            }
+            let mut expected = Vec::from(&record[..]);
+            reference_key
+                .seal_in_place_append_tag(
+                    Nonce::assume_unique_for_key(nonce),
+                    Aad::empty(),
+                    &mut expected,
+                )
+                .unwrap();
+            assert_eq!(expected, in_out);
+
+            let plaintext = opening_key
+                .open_in_place(Aad::empty(), &mut in_out)
+                .unwrap();
+            assert_eq!(&record[..], plaintext);
+        }
+    }
+
+    struct RepeatingNonce;
+
+    impl NonceSequence for RepeatingNonce {
+        fn advance(&mut self) -> Result<Nonce, Unspecified> {
+            Ok(Nonce::assume_unique_for_key([0u8; NONCE_LEN]))
+        }
+    }
+
+    #[test]
+    fn test_repeated_nonce_rejected() {
+        for protocol in [TlsProtocolId::TLS12, TlsProtocolId::TLS13] {
+            let mut key =
+                TlsRecordSealingKey::new(&AES_128_GCM, protocol, &[0u8; 16], RepeatingNonce)
+                    .unwrap();
+            let mut in_out = Vec::from(&b"record"[..]);
+            key.seal_in_place_append_tag(Aad::empty(), &mut in_out)
+                .unwrap();
+            let mut in_out = Vec::from(&b"record"[..]);
+            assert!(key
+                .seal_in_place_append_tag(Aad::empty(), &mut in_out)
+                .is_err());
+        }
+    }
+}
@@ -52,16 +52,20 @@
 use crate::ec::{
     ec_group_from_nid, ec_key_from_public_point, ec_key_generate, ec_point_from_bytes,
 };
-use crate::error::Unspecified;
-#[cfg(test)]
-use crate::ptr::DetachableLcPtr;
-use crate::ptr::{ConstPointer, LcPtr};
+use crate::error::{KeyRejected, Unspecified};
+#[cfg(feature = "pem")]
+use crate::pem;
+use crate::pkcs8::{Document, Version};
+use crate::ptr::{ConstPointer, DetachableLcPtr, LcPtr};
 use crate::rand::SecureRandom;
-use crate::{ec, test};
+use crate::{cbb, ec, spki, test};
 use aws_lc::{
-    ECDH_compute_key, EC_GROUP_cmp, EC_GROUP_get_curve_name, EC_GROUP_get_degree,
-    EC_KEY_get0_group, EC_KEY_get0_public_key, NID_X9_62_prime256v1, NID_secp384r1, NID_secp521r1,
-    X25519_keypair, X25519_public_from_private, EC_KEY, NID_X25519,
+    BN_bn2bin_padded, ECDH_compute_key, EC_GROUP_cmp, EC_GROUP_get_curve_name, EC_GROUP_get_degree,
+    EC_KEY_get0_group, EC_KEY_get0_private_key, EC_KEY_get0_public_key, EC_KEY_marshal_private_key,
+    EVP_PKEY_get_raw_private_key, EVP_PKEY_get_raw_public_key, EVP_PKEY_new,
+    EVP_PKEY_new_raw_private_key, EVP_PKEY_new_raw_public_key, EVP_PKEY_set1_EC_KEY,
+    NID_X9_62_prime256v1, NID_secp384r1, NID_secp521r1, X25519_keypair, X25519_public_from_private,
+    EC_KEY, EVP_PKEY, EVP_PKEY_X25519, NID_X25519,
 };
 
 use core::fmt;
@@ -89,6 +93,16 @@ impl AlgorithmID {
         }
     }
 
+    #[inline]
+    fn private_key_len(&self) -> usize {
+        match self {
+            AlgorithmID::ECDH_P256 => ECDH_P256_PRIVATE_KEY_LEN,
+            AlgorithmID::ECDH_P384 => ECDH_P384_PRIVATE_KEY_LEN,
+            AlgorithmID::ECDH_P521 => ECDH_P521_PRIVATE_KEY_LEN,
+            AlgorithmID::X25519 => X25519_PRIVATE_KEY_LEN,
+        }
+    }
+
     #[inline]
     fn pub_key_len(&self) -> usize {
         match self {
@@ -151,9 +165,7 @@ pub static X25519: Algorithm = Algorithm {
     id: AlgorithmID::X25519,
 };
 const X25519_PRIVATE_KEY_LEN: usize = aws_lc::X25519_PRIVATE_KEY_LEN as usize;
-#[cfg(test)]
 const ECDH_P256_PRIVATE_KEY_LEN: usize = 32;
-#[cfg(test)]
 const ECDH_P384_PRIVATE_KEY_LEN: usize = 48;
 const ECDH_P521_PRIVATE_KEY_LEN: usize = 66;
 const X25519_PUBLIC_VALUE_LEN: usize = aws_lc::X25519_PUBLIC_VALUE_LEN as usize;
@@ -192,38 +204,8 @@ impl KeyInner {
             KeyInner::X25519(..) => &X25519,
         }
     }
-}
-
-unsafe impl Send for EphemeralPrivateKey {}
-
-// https://github.com/awslabs/aws-lc/blob/main/include/openssl/ec_key.h#L88
-// An |EC_KEY| object represents a public or private EC key. A given object may
-// be used concurrently on multiple threads by non-mutating functions, provided
-// no other thread is concurrently calling a mutating function. Unless otherwise
-// documented, functions which take a |const| pointer are non-mutating and
-// functions which take a non-|const| pointer are mutating.
-unsafe impl Sync for EphemeralPrivateKey {}
-
-impl Debug for EphemeralPrivateKey {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str(&format!(
-            "EphemeralPrivateKey {{ algorithm: {:?} }}",
-            self.inner_key.algorithm()
-        ))
-    }
-}
 
-impl EphemeralPrivateKey {
-    #[inline]
-    /// Generate a new ephemeral private key for the given algorithm.
-    ///
-    /// # *ring* Compatibility
-    ///  Our implementation ignores the `SecureRandom` parameter.
-    ///
-    /// # Errors
-    /// `error::Unspecified` when operation fails due to internal error.
-    ///
-    pub fn generate(alg: &'static Algorithm, _rng: &dyn SecureRandom) -> Result<Self, Unspecified> {
+    fn generate(alg: &'static Algorithm) -> Result<Self, Unspecified> {
         match alg.id {
             AlgorithmID::X25519 => {
                 let mut priv_key = [0u8; X25519_PRIVATE_KEY_LEN];
@@ -231,116 +213,88 @@ impl EphemeralPrivateKey {
                 unsafe {
                     X25519_keypair(pub_key.as_mut_ptr(), priv_key.as_mut_ptr());
                 }
-                Ok(EphemeralPrivateKey {
-                    inner_key: KeyInner::X25519(priv_key),
-                })
+                Ok(KeyInner::X25519(priv_key))
             }
             AlgorithmID::ECDH_P256 => unsafe {
                 let ec_key = ec_key_generate(ECDH_P256.id.nid())?;
-                Ok(EphemeralPrivateKey {
-                    inner_key: KeyInner::ECDH_P256(LcPtr::from(ec_key)),
-                })
+                Ok(KeyInner::ECDH_P256(LcPtr::from(ec_key)))
             },
             AlgorithmID::ECDH_P384 => unsafe {
                 let ec_key = ec_key_generate(ECDH_P384.id.nid())?;
-                Ok(EphemeralPrivateKey {
-                    inner_key: KeyInner::ECDH_P384(LcPtr::from(ec_key)),
-                })
+                Ok(KeyInner::ECDH_P384(LcPtr::from(ec_key)))
             },
             AlgorithmID::ECDH_P521 => unsafe {
                 let ec_key = ec_key_generate(ECDH_P521.id.nid())?;
-                Ok(EphemeralPrivateKey {
-                    inner_key: KeyInner::ECDH_P521(LcPtr::from(ec_key)),
-                })
+                Ok(KeyInner::ECDH_P521(LcPtr::from(ec_key)))
             },
         }
     }
 
-    #[cfg(test)]
-    #[allow(clippy::missing_errors_doc)]
-    pub fn generate_for_test(
-        alg: &'static Algorithm,
-        rng: &dyn SecureRandom,
-    ) -> Result<Self, Unspecified> {
-        match alg.id {
-            AlgorithmID::X25519 => {
-                let mut priv_key = [0u8; X25519_PRIVATE_KEY_LEN];
-                rng.fill(&mut priv_key)?;
-                Ok(Self::from_x25519_private_key(&priv_key))
-            }
-            AlgorithmID::ECDH_P256 => {
-                let mut priv_key = [0u8; ECDH_P256_PRIVATE_KEY_LEN];
-                rng.fill(&mut priv_key)?;
-                Self::from_p256_private_key(&priv_key)
-            }
-            AlgorithmID::ECDH_P384 => {
-                let mut priv_key = [0u8; ECDH_P384_PRIVATE_KEY_LEN];
-                rng.fill(&mut priv_key)?;
-                Self::from_p384_private_key(&priv_key)
-            }
-            AlgorithmID::ECDH_P521 => {
-                let mut priv_key = [0u8; ECDH_P521_PRIVATE_KEY_LEN];
-                rng.fill(&mut priv_key)?;
-                Self::from_p521_private_key(&priv_key)
-            }
+    fn from_private_key(alg: &'static Algorithm, priv_key: &[u8]) -> Result<Self, KeyRejected> {
+        let expected_len = alg.id.private_key_len();
+        if priv_key.len() < expected_len {
+            return Err(KeyRejected::too_small());
         }
+        if priv_key.len() > expected_len {
+            return Err(KeyRejected::too_large());
+        }
+        if let AlgorithmID::X25519 = alg.id {
+            let mut x25519_priv_key = [0u8; X25519_PRIVATE_KEY_LEN];
+            x25519_priv_key.copy_from_slice(priv_key);
+            return Ok(KeyInner::X25519(x25519_priv_key));
+        }
+        let ec_key = unsafe {
+            let ec_group = ec_group_from_nid(alg.id.nid())?;
+            let priv_key = DetachableLcPtr::try_from(priv_key)?;
+            ec::ec_key_from_private(&ec_group.as_const(), &priv_key.as_const())
+                .map_err(|_| KeyRejected::invalid_encoding())?
+        };
+        Ok(Self::from_ec_key(alg, LcPtr::from(ec_key)))
     }
 
-    #[cfg(test)]
-    fn from_x25519_private_key(priv_key: &[u8; X25519_PRIVATE_KEY_LEN]) -> Self {
-        let inner_key = KeyInner::X25519(*priv_key);
-        EphemeralPrivateKey { inner_key }
-    }
-
-    #[cfg(test)]
-    fn from_p256_private_key(priv_key: &[u8]) -> Result<Self, Unspecified> {
+    fn from_pkcs8(alg: &'static Algorithm, pkcs8: &[u8]) -> Result<Self, KeyRejected> {
+        let evp_pkey = LcPtr::try_from(pkcs8)?;
+        if let AlgorithmID::X25519 = alg.id {
+            if evp_pkey.id() != EVP_PKEY_X25519 {
+                return Err(KeyRejected::wrong_algorithm());
+            }
+            let mut priv_key = [0u8; X25519_PRIVATE_KEY_LEN];
+            let mut priv_key_len = X25519_PRIVATE_KEY_LEN;
+            if 1 != unsafe {
+                EVP_PKEY_get_raw_private_key(*evp_pkey, priv_key.as_mut_ptr(), &mut priv_key_len)
+            } || priv_key_len != X25519_PRIVATE_KEY_LEN
+            {
+                return Err(KeyRejected::invalid_encoding());
+            }
+            return Ok(KeyInner::X25519(priv_key));
+        }
+        let ec_key = evp_pkey.get_ec_key()?;
         unsafe {
-            let ec_group = ec_group_from_nid(ECDH_P256.id.nid())?;
-            let priv_key = DetachableLcPtr::try_from(priv_key)?;
-
-            let ec_key = ec::ec_key_from_private(&ec_group.as_const(), &priv_key.as_const())?;
-            let ec_key = LcPtr::from(ec_key);
-            Ok(EphemeralPrivateKey {
-                inner_key: KeyInner::ECDH_P256(ec_key),
-            })
+            ec::validate_ec_key(&ec_key.as_const(), alg.id.nid())?;
         }
+        Ok(Self::from_ec_key(alg, ec_key))
     }
 
-    #[cfg(test)]
-    fn from_p384_private_key(priv_key: &[u8]) -> Result<Self, Unspecified> {
-        unsafe {
-            let ec_group = ec_group_from_nid(ECDH_P384.id.nid())?;
-            let priv_key = DetachableLcPtr::try_from(priv_key)?;
-
-            let ec_key = ec::ec_key_from_private(&ec_group.as_const(), &priv_key.as_const())?;
-            let ec_key = LcPtr::from(ec_key);
-            Ok(EphemeralPrivateKey {
-                inner_key: KeyInner::ECDH_P384(ec_key),
-            })
+    fn from_sec1_der(alg: &'static Algorithm, sec1: &[u8]) -> Result<Self, KeyRejected> {
+        if let AlgorithmID::X25519 = alg.id {
+            return Err(KeyRejected::wrong_algorithm());
         }
+        let ec_key = unsafe { ec::ec_key_from_sec1_der(alg.id.nid(), sec1)? };
+        Ok(Self::from_ec_key(alg, ec_key))
     }
 
-    #[cfg(test)]
-    fn from_p521_private_key(priv_key: &[u8]) -> Result<Self, Unspecified> {
-        unsafe {
-            let ec_group = ec_group_from_nid(ECDH_P521.id.nid())?;
-            let priv_key = DetachableLcPtr::try_from(priv_key)?;
-
-            let ec_key = ec::ec_key_from_private(&ec_group.as_const(), &priv_key.as_const())?;
-            let ec_key = LcPtr::from(ec_key);
-            Ok(EphemeralPrivateKey {
-                inner_key: KeyInner::ECDH_P521(ec_key),
-            })
+    #[inline]
+    fn from_ec_key(alg: &'static Algorithm, ec_key: LcPtr<*mut EC_KEY>) -> Self {
+        match alg.id {
+            AlgorithmID::ECDH_P256 => KeyInner::ECDH_P256(ec_key),
+            AlgorithmID::ECDH_P384 => KeyInner::ECDH_P384(ec_key),
+            AlgorithmID::ECDH_P521 => KeyInner::ECDH_P521(ec_key),
+            AlgorithmID::X25519 => unreachable!(),
         }
     }
 
-    /// Computes the public key from the private key.
-    ///
-    /// # Errors
-    /// `error::Unspecified` when operation fails due to internal error.
-    ///
-    pub fn compute_public_key(&self) -> Result<PublicKey, Unspecified> {
-        match &self.inner_key {
+    fn compute_public_key(&self) -> Result<PublicKey, Unspecified> {
+        match self {
             KeyInner::ECDH_P256(ec_key)
             | KeyInner::ECDH_P384(ec_key)
             | KeyInner::ECDH_P521(ec_key) => {
@@ -370,6 +324,123 @@ impl EphemeralPrivateKey {
         }
     }
 
+    fn private_key_bytes(&self) -> Result<PrivateKeyBytes, Unspecified> {
+        let mut bytes = PrivateKeyBytes {
+            bytes: [0u8; MAX_PRIVATE_KEY_LEN],
+            len: self.algorithm().id.private_key_len(),
+        };
+        match self {
+            KeyInner::ECDH_P256(ec_key)
+            | KeyInner::ECDH_P384(ec_key)
+            | KeyInner::ECDH_P521(ec_key) => unsafe {
+                let priv_key = ConstPointer::new(EC_KEY_get0_private_key(**ec_key))?;
+                if 1 != BN_bn2bin_padded(bytes.bytes.as_mut_ptr(), bytes.len, *priv_key) {
+                    return Err(Unspecified);
+                }
+            },
+            KeyInner::X25519(priv_key) => {
+                bytes.bytes[..X25519_PRIVATE_KEY_LEN].copy_from_slice(priv_key);
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn to_evp_pkey(&self) -> Result<LcPtr<*mut EVP_PKEY>, Unspecified> {
+        match self {
+            KeyInner::ECDH_P256(ec_key)
+            | KeyInner::ECDH_P384(ec_key)
+            | KeyInner::ECDH_P521(ec_key) => unsafe {
+                let evp_pkey = LcPtr::new(EVP_PKEY_new())?;
+                if 1 != EVP_PKEY_set1_EC_KEY(*evp_pkey, **ec_key) {
+                    return Err(Unspecified);
+                }
+                Ok(evp_pkey)
+            },
+            KeyInner::X25519(priv_key) => unsafe {
+                Ok(LcPtr::new(EVP_PKEY_new_raw_private_key(
+                    EVP_PKEY_X25519,
+                    null_mut(),
+                    priv_key.as_ptr(),
+                    X25519_PRIVATE_KEY_LEN,
+                ))?)
+            },
+        }
+    }
+
+    fn to_sec1_der(&self) -> Result<Sec1Document, Unspecified> {
+        match self {
+            KeyInner::ECDH_P256(ec_key)
+            | KeyInner::ECDH_P384(ec_key)
+            | KeyInner::ECDH_P521(ec_key) => unsafe {
+                let mut cbb = cbb::build_CBB(ec::PKCS8_DOCUMENT_MAX_LEN);
+                if 1 != EC_KEY_marshal_private_key(cbb.as_mut_ptr(), **ec_key, 0) {
+                    return Err(Unspecified);
+                }
+                Ok(Sec1Document(cbb.finish()?))
+            },
+            KeyInner::X25519(..) => Err(Unspecified),
+        }
+    }
+}
+
+unsafe impl Send for EphemeralPrivateKey {}
+
+// https://github.com/awslabs/aws-lc/blob/main/include/openssl/ec_key.h#L88
+// An |EC_KEY| object represents a public or private EC key. A given object may
+// be used concurrently on multiple threads by non-mutating functions, provided
+// no other thread is concurrently calling a mutating function. Unless otherwise
+// documented, functions which take a |const| pointer are non-mutating and
+// functions which take a non-|const| pointer are mutating.
+unsafe impl Sync for EphemeralPrivateKey {}
+
+impl Debug for EphemeralPrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "EphemeralPrivateKey {{ algorithm: {:?} }}",
+            self.inner_key.algorithm()
+        ))
+    }
+}
+
+impl EphemeralPrivateKey {
+    #[inline]
+    /// Generate a new ephemeral private key for the given algorithm.
+    ///
+    /// # *ring* Compatibility
+    ///  Our implementation ignores the `SecureRandom` parameter.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    ///
+    pub fn generate(alg: &'static Algorithm, _rng: &dyn SecureRandom) -> Result<Self, Unspecified> {
+        Ok(EphemeralPrivateKey {
+            inner_key: KeyInner::generate(alg)?,
+        })
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::missing_errors_doc)]
+    pub fn generate_for_test(
+        alg: &'static Algorithm,
+        rng: &dyn SecureRandom,
+    ) -> Result<Self, Unspecified> {
+        let mut priv_key = [0u8; MAX_PRIVATE_KEY_LEN];
+        let priv_key = &mut priv_key[..alg.id.private_key_len()];
+        rng.fill(priv_key)?;
+        Ok(EphemeralPrivateKey {
+            inner_key: KeyInner::from_private_key(alg, priv_key)?,
+        })
+    }
+
+    /// Computes the public key from the private key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    ///
+    pub fn compute_public_key(&self) -> Result<PublicKey, Unspecified> {
+        self.inner_key.compute_public_key()
+    }
+
     /// The algorithm for the private key.
     #[inline]
     #[must_use]
@@ -378,6 +449,186 @@ impl EphemeralPrivateKey {
     }
 }
 
+/// A private key for use with `agree`. Unlike an `EphemeralPrivateKey`, a
+/// `PrivateKey` is not consumed by key agreement, so the same key can be used
+/// for more than one key agreement, e.g. as a static key.
+///
+/// PKCS#8 v1 and v2 documents can both be imported, but keys are only exported
+/// as PKCS#8 v1. AWS-LC's `EVP_marshal_private_key_v2` can only encode Ed25519
+/// keys, so it cannot produce a v2 document for `X25519` or ECDH keys.
+pub struct PrivateKey {
+    inner_key: KeyInner,
+}
+
+unsafe impl Send for PrivateKey {}
+
+// See the comment on `EphemeralPrivateKey`'s `Sync` implementation.
+unsafe impl Sync for PrivateKey {}
+
+impl Debug for PrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "PrivateKey {{ algorithm: {:?} }}",
+            self.inner_key.algorithm()
+        ))
+    }
+}
+
+impl PrivateKey {
+    /// Generates a new private key for the given algorithm.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    ///
+    pub fn generate(alg: &'static Algorithm) -> Result<Self, Unspecified> {
+        Ok(PrivateKey {
+            inner_key: KeyInner::generate(alg)?,
+        })
+    }
+
+    /// Constructs a private key from its raw encoding.
+    ///
+    /// For `ECDH_P256`, `ECDH_P384` and `ECDH_P521`, `priv_key` is the big-endian
+    /// encoding of the private scalar, padded with leading zeros to the size of
+    /// the curve's order. For `X25519`, `priv_key` is the 32-byte private key as
+    /// described in [RFC 7748].
+    ///
+    /// [RFC 7748]: https://tools.ietf.org/html/rfc7748
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `priv_key` is not a valid private key for `alg`.
+    ///
+    pub fn from_private_key(alg: &'static Algorithm, priv_key: &[u8]) -> Result<Self, KeyRejected> {
+        Ok(PrivateKey {
+            inner_key: KeyInner::from_private_key(alg, priv_key)?,
+        })
+    }
+
+    /// Constructs a private key from a PKCS#8 v1 or v2 document.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `pkcs8` is not a valid PKCS#8 document, or does
+    /// not contain a valid private key for `alg`.
+    ///
+    pub fn from_pkcs8(alg: &'static Algorithm, pkcs8: &[u8]) -> Result<Self, KeyRejected> {
+        Ok(PrivateKey {
+            inner_key: KeyInner::from_pkcs8(alg, pkcs8)?,
+        })
+    }
+
+    /// Constructs a private key from a DER-encoded SEC1 `ECPrivateKey` structure,
+    /// as specified in [RFC 5915].
+    ///
+    /// [RFC 5915]: https://tools.ietf.org/html/rfc5915
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `sec1` is not a valid `ECPrivateKey` for `alg`. `X25519`
+    /// keys have no SEC1 encoding and are always rejected.
+    ///
+    pub fn from_sec1_der(alg: &'static Algorithm, sec1: &[u8]) -> Result<Self, KeyRejected> {
+        Ok(PrivateKey {
+            inner_key: KeyInner::from_sec1_der(alg, sec1)?,
+        })
+    }
+
+    /// Serializes the private key in the raw encoding accepted by
+    /// `from_private_key`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    ///
+    pub fn private_key_bytes(&self) -> Result<PrivateKeyBytes, Unspecified> {
+        self.inner_key.private_key_bytes()
+    }
+
+    /// Serializes the private key as a PKCS#8 v1 document. There is no v2
+    /// export; see the documentation of `PrivateKey`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    ///
+    pub fn to_pkcs8v1(&self) -> Result<Document, Unspecified> {
+        self.inner_key
+            .to_evp_pkey()?
+            .marshall_private_key(Version::V1)
+    }
+
+    /// Serializes the private key as a DER-encoded SEC1 `ECPrivateKey` structure,
+    /// including the curve parameters and the public key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` for `X25519` keys, which have no SEC1 encoding, or when
+    /// operation fails due to internal error.
+    ///
+    pub fn to_sec1_der(&self) -> Result<Sec1Document, Unspecified> {
+        self.inner_key.to_sec1_der()
+    }
+
+    /// Computes the public key from the private key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    ///
+    pub fn compute_public_key(&self) -> Result<PublicKey, Unspecified> {
+        self.inner_key.compute_public_key()
+    }
+
+    /// The algorithm for the private key.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.inner_key.algorithm()
+    }
+}
+
+const MAX_PRIVATE_KEY_LEN: usize = ECDH_P521_PRIVATE_KEY_LEN;
+
+/// The raw encoding of a private key. It is zeroized when dropped.
+pub struct PrivateKeyBytes {
+    bytes: [u8; MAX_PRIVATE_KEY_LEN],
+    len: usize,
+}
+
+impl AsRef<[u8]> for PrivateKeyBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl Drop for PrivateKeyBytes {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl Debug for PrivateKeyBytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("PrivateKeyBytes()")
+    }
+}
+
+/// A DER-encoded SEC1 `ECPrivateKey` structure. It is zeroized when dropped.
+pub struct Sec1Document(Box<[u8]>);
+
+impl AsRef<[u8]> for Sec1Document {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl Drop for Sec1Document {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Debug for Sec1Document {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("Sec1Document()")
+    }
+}
 const MAX_PUBLIC_KEY_LEN: usize = ec::PUBLIC_KEY_MAX_LEN;
 
 /// A public key for key agreement.
@@ -388,11 +639,115 @@ pub struct PublicKey {
 }
 
 impl PublicKey {
+    pub(crate) fn new(alg: &'static Algorithm, bytes: &[u8]) -> Result<Self, Unspecified> {
+        if bytes.len() != alg.id.pub_key_len() {
+            return Err(Unspecified);
+        }
+        let mut public_key = [0u8; MAX_PUBLIC_KEY_LEN];
+        public_key[..bytes.len()].copy_from_slice(bytes);
+        Ok(PublicKey {
+            alg,
+            public_key,
+            len: bytes.len(),
+        })
+    }
+
     /// The algorithm for the public key.
     #[must_use]
     pub fn algorithm(&self) -> &'static Algorithm {
         self.alg
     }
+
+    /// Parses a DER-encoded X.509 `SubjectPublicKeyInfo` containing an X25519 or ECDH
+    /// public key. The algorithm is detected from the encoding.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `spki` is not a valid encoding, or if it does not contain
+    /// a public key for a supported algorithm.
+    pub fn from_der(spki: &[u8]) -> Result<Self, KeyRejected> {
+        let evp_pkey = LcPtr::<*mut EVP_PKEY>::parse_public_key(spki)?;
+        if evp_pkey.id() == EVP_PKEY_X25519 {
+            let mut public_key = [0u8; MAX_PUBLIC_KEY_LEN];
+            let mut out_len = X25519_PUBLIC_VALUE_LEN;
+            if 1 != unsafe {
+                EVP_PKEY_get_raw_public_key(*evp_pkey, public_key.as_mut_ptr(), &mut out_len)
+            } {
+                return Err(KeyRejected::invalid_encoding());
+            }
+            return Ok(PublicKey {
+                alg: &X25519,
+                public_key,
+                len: out_len,
+            });
+        }
+
+        let ec_key = evp_pkey.get_ec_key()?;
+        let nid = unsafe {
+            let ec_group = ConstPointer::new(EC_KEY_get0_group(*ec_key))?;
+            EC_GROUP_get_curve_name(*ec_group)
+        };
+        let alg = [&ECDH_P256, &ECDH_P384, &ECDH_P521]
+            .into_iter()
+            .find(|alg| alg.id.nid() == nid)
+            .ok_or_else(KeyRejected::wrong_algorithm)?;
+        let mut public_key = [0u8; MAX_PUBLIC_KEY_LEN];
+        let len = unsafe { ec::marshal_public_key_to_buffer(&mut public_key, &ec_key.as_const()) }
+            .map_err(|Unspecified| KeyRejected::unexpected_error())?;
+        Ok(PublicKey {
+            alg,
+            public_key,
+            len,
+        })
+    }
+
+    /// Serializes this public key as a DER-encoded X.509 `SubjectPublicKeyInfo`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn as_der(&self) -> Result<spki::Document, Unspecified> {
+        let evp_pkey = match self.alg.id {
+            AlgorithmID::X25519 => LcPtr::new(unsafe {
+                EVP_PKEY_new_raw_public_key(
+                    EVP_PKEY_X25519,
+                    null_mut(),
+                    self.public_key.as_ptr(),
+                    self.len,
+                )
+            })?,
+            AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P384 | AlgorithmID::ECDH_P521 => unsafe {
+                ec::evp_pkey_from_public_point(self.alg.id.nid(), self.as_ref())?
+            },
+        };
+        evp_pkey.marshall_public_key()
+    }
+
+    /// Parses a PEM-encoded X.509 `SubjectPublicKeyInfo` (`PUBLIC KEY`) as described for
+    /// `from_der`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the PEM label is for another kind of key, or as described
+    /// for `from_der`.
+    #[cfg(feature = "pem")]
+    pub fn from_pem(pem: &str) -> Result<Self, KeyRejected> {
+        let (label, der) = pem::decode(pem)?;
+        match label {
+            pem::Label::PublicKey => Self::from_der(&der),
+            pem::Label::RsaPublicKey => Err(KeyRejected::wrong_algorithm()),
+            pem::Label::PrivateKey | pem::Label::RsaPrivateKey | pem::Label::EcPrivateKey => {
+                Err(KeyRejected::unexpected_pem_label())
+            }
+        }
+    }
+
+    /// Serializes this public key as a PEM-encoded X.509 `SubjectPublicKeyInfo`
+    /// (`PUBLIC KEY`).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "pem")]
+    pub fn to_pem(&self) -> Result<pem::Document, Unspecified> {
+        pem::encode(pem::Label::PublicKey, self.as_der()?.as_ref())
+    }
 }
 
 impl Debug for PublicKey {
@@ -487,13 +842,50 @@ impl<B: AsRef<[u8]>> UnparsedPublicKey<B> {
 ///
 #[inline]
 #[allow(clippy::needless_pass_by_value)]
-#[allow(clippy::missing_panics_doc)]
 pub fn agree_ephemeral<B: AsRef<[u8]>, F, R, E>(
     my_private_key: EphemeralPrivateKey,
     peer_public_key: &UnparsedPublicKey<B>,
     error_value: E,
     kdf: F,
 ) -> Result<R, E>
+where
+    F: FnOnce(&[u8]) -> Result<R, E>,
+{
+    agree_inner(&my_private_key.inner_key, peer_public_key, error_value, kdf)
+}
+
+/// Performs a key agreement with a private key and the given public key.
+///
+/// Unlike `agree_ephemeral`, `agree` borrows `my_private_key`, so the same key
+/// can be used for more than one key agreement.
+///
+/// `peer_public_key`, `error_value` and `kdf` are handled as described for
+/// `agree_ephemeral`.
+///
+/// # Errors
+/// `error_value` on internal failure.
+///
+#[inline]
+pub fn agree<B: AsRef<[u8]>, F, R, E>(
+    my_private_key: &PrivateKey,
+    peer_public_key: &UnparsedPublicKey<B>,
+    error_value: E,
+    kdf: F,
+) -> Result<R, E>
+where
+    F: FnOnce(&[u8]) -> Result<R, E>,
+{
+    agree_inner(&my_private_key.inner_key, peer_public_key, error_value, kdf)
+}
+
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+fn agree_inner<B: AsRef<[u8]>, F, R, E>(
+    my_private_key: &KeyInner,
+    peer_public_key: &UnparsedPublicKey<B>,
+    error_value: E,
+    kdf: F,
+) -> Result<R, E>
 where
     F: FnOnce(&[u8]) -> Result<R, E>,
 {
@@ -511,7 +903,7 @@ where
 
     let mut buffer = [0u8; MAX_AGREEMENT_SECRET_LEN];
 
-    let secret: &[u8] = match &my_private_key.inner_key {
+    let secret: &[u8] = match my_private_key {
         KeyInner::X25519(priv_key, ..) => {
             let mut pub_key = [0u8; X25519_PUBLIC_VALUE_LEN];
             pub_key.copy_from_slice(peer_pub_bytes);
@@ -970,6 +1362,207 @@ mod tests {
         );
     }
 
+    #[test]
+    fn agreement_agree() {
+        test::run(
+            test_file!("data/agreement_tests.txt"),
+            |section, test_case| {
+                assert_eq!(section, "");
+
+                let curve_name = test_case.consume_string("Curve");
+                let alg = alg_from_curve_name(&curve_name);
+                let peer_public =
+                    agreement::UnparsedPublicKey::new(alg, test_case.consume_bytes("PeerQ"));
+
+                if test_case.consume_optional_string("Error").is_none() {
+                    let my_private_bytes = test_case.consume_bytes("D");
+                    let my_public = test_case.consume_bytes("MyQ");
+                    let output = test_case.consume_bytes("Output");
+
+                    let my_private =
+                        agreement::PrivateKey::from_private_key(alg, &my_private_bytes)?;
+                    assert_eq!(my_private.algorithm(), alg);
+                    assert_eq!(
+                        my_private.private_key_bytes()?.as_ref(),
+                        &my_private_bytes[..]
+                    );
+
+                    let computed_public = my_private.compute_public_key()?;
+                    assert_eq!(computed_public.as_ref(), &my_public[..]);
+
+                    // The same private key can be used for more than one agreement.
+                    for _ in 0..2 {
+                        agreement::agree(&my_private, &peer_public, (), |key_material| {
+                            assert_eq!(key_material, &output[..]);
+                            Ok(())
+                        })
+                        .unwrap();
+                    }
+                } else {
+                    let my_private = agreement::PrivateKey::generate(alg)?;
+                    assert!(agreement::agree(
+                        &my_private,
+                        &peer_public,
+                        (),
+                        |_| -> Result<(), ()> {
+                            panic!("The KDF was called with an invalid public key.")
+                        }
+                    )
+                    .is_err());
+                }
+
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn agreement_private_key() {
+        for alg in [
+            &agreement::ECDH_P256,
+            &agreement::ECDH_P384,
+            &agreement::ECDH_P521,
+            &agreement::X25519,
+        ] {
+            let my_private = agreement::PrivateKey::generate(alg).unwrap();
+            let peer_private = agreement::PrivateKey::generate(alg).unwrap();
+            let my_public = my_private.compute_public_key().unwrap();
+            let peer_public = peer_private.compute_public_key().unwrap();
+
+            let my_secret = agreement::agree(
+                &my_private,
+                &agreement::UnparsedPublicKey::new(alg, &peer_public),
+                (),
+                |key_material| Ok(key_material.to_vec()),
+            )
+            .unwrap();
+            let peer_secret = agreement::agree(
+                &peer_private,
+                &agreement::UnparsedPublicKey::new(alg, &my_public),
+                (),
+                |key_material| Ok(key_material.to_vec()),
+            )
+            .unwrap();
+            assert_eq!(my_secret, peer_secret);
+
+            let private_key_bytes = my_private.private_key_bytes().unwrap();
+            let imported =
+                agreement::PrivateKey::from_private_key(alg, private_key_bytes.as_ref()).unwrap();
+            assert_eq!(
+                my_public.as_ref(),
+                imported.compute_public_key().unwrap().as_ref()
+            );
+
+            assert_eq!(
+                "TooSmall",
+                agreement::PrivateKey::from_private_key(alg, &private_key_bytes.as_ref()[1..])
+                    .unwrap_err()
+                    .description_()
+            );
+            let mut too_long = private_key_bytes.as_ref().to_vec();
+            too_long.push(0);
+            assert_eq!(
+                "TooLarge",
+                agreement::PrivateKey::from_private_key(alg, &too_long)
+                    .unwrap_err()
+                    .description_()
+            );
+        }
+
+        // A scalar of zero is not a valid private key.
+        assert!(
+            agreement::PrivateKey::from_private_key(&agreement::ECDH_P256, &[0u8; 32]).is_err()
+        );
+
+        let private_key = agreement::PrivateKey::generate(&agreement::X25519).unwrap();
+        assert_eq!(
+            format!("{:?}", &private_key),
+            "PrivateKey { algorithm: Algorithm { curve: Curve25519 } }"
+        );
+        assert_eq!(
+            "PrivateKeyBytes()",
+            format!("{:?}", private_key.private_key_bytes().unwrap())
+        );
+        test::compile_time_assert_send::<agreement::PrivateKey>();
+        test::compile_time_assert_sync::<agreement::PrivateKey>();
+    }
+
+    #[test]
+    fn agreement_private_key_encodings() {
+        test::run(
+            test_file!("data/agreement_private_key_tests.txt"),
+            |section, test_case| {
+                assert_eq!(section, "");
+
+                let curve_name = test_case.consume_string("Curve");
+                let alg = alg_from_curve_name(&curve_name);
+                let private_key_bytes = test_case.consume_bytes("D");
+                let pkcs8 = test_case.consume_bytes("PKCS8");
+                let sec1 = test_case.consume_optional_bytes("SEC1");
+
+                let expected_public_key =
+                    agreement::PrivateKey::from_private_key(alg, &private_key_bytes)?
+                        .compute_public_key()?;
+
+                let private_key = agreement::PrivateKey::from_pkcs8(alg, &pkcs8)?;
+                assert_eq!(
+                    private_key.private_key_bytes()?.as_ref(),
+                    &private_key_bytes[..]
+                );
+                assert_eq!(
+                    private_key.compute_public_key()?.as_ref(),
+                    expected_public_key.as_ref()
+                );
+
+                let reencoded = private_key.to_pkcs8v1()?;
+                let private_key = agreement::PrivateKey::from_pkcs8(alg, reencoded.as_ref())?;
+                assert_eq!(
+                    private_key.private_key_bytes()?.as_ref(),
+                    &private_key_bytes[..]
+                );
+
+                if let Some(sec1) = sec1 {
+                    let private_key = agreement::PrivateKey::from_sec1_der(alg, &sec1)?;
+                    assert_eq!(
+                        private_key.private_key_bytes()?.as_ref(),
+                        &private_key_bytes[..]
+                    );
+                    let reencoded = private_key.to_sec1_der()?;
+                    let private_key =
+                        agreement::PrivateKey::from_sec1_der(alg, reencoded.as_ref())?;
+                    assert_eq!(
+                        private_key.compute_public_key()?.as_ref(),
+                        expected_public_key.as_ref()
+                    );
+
+                    let mut trailing = sec1.clone();
+                    trailing.push(0);
+                    assert!(agreement::PrivateKey::from_sec1_der(alg, &trailing).is_err());
+                    assert!(
+                        agreement::PrivateKey::from_sec1_der(&agreement::X25519, &sec1).is_err()
+                    );
+                } else {
+                    assert!(private_key.to_sec1_der().is_err());
+                    assert!(agreement::PrivateKey::from_sec1_der(alg, &pkcs8).is_err());
+                }
+
+                // A key is rejected when used with another algorithm.
+                for other_alg in [
+                    &agreement::ECDH_P256,
+                    &agreement::ECDH_P384,
+                    &agreement::ECDH_P521,
+                    &agreement::X25519,
+                ] {
+                    if other_alg != alg {
+                        assert!(agreement::PrivateKey::from_pkcs8(other_alg, &pkcs8).is_err());
+                    }
+                }
+
+                Ok(())
+            },
+        );
+    }
+
     fn h(s: &str) -> Vec<u8> {
         match test::from_hex(s) {
             Ok(v) => v,
@@ -1,7 +1,9 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-use aws_lc::{CBB_cleanup, CBB_init, CBB};
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use aws_lc::{CBB_cleanup, CBB_finish, CBB_init, CBB};
 use std::mem::MaybeUninit;
 
 pub(crate) struct LcCBB(CBB);
@@ -10,6 +12,24 @@ impl LcCBB {
     pub(crate) fn as_mut_ptr(&mut self) -> *mut CBB {
         &mut self.0
     }
+
+    /// Finishes the CBB and returns a copy of its contents.
+    pub(crate) fn finish(mut self) -> Result<Box<[u8]>, Unspecified> {
+        unsafe {
+            let mut out_ptr = MaybeUninit::<*mut u8>::uninit();
+            let mut out_len = MaybeUninit::<usize>::uninit();
+            if 1 != CBB_finish(
+                self.as_mut_ptr(),
+                out_ptr.as_mut_ptr(),
+                out_len.as_mut_ptr(),
+            ) {
+                return Err(Unspecified);
+            }
+            let out_ptr = LcPtr::new(out_ptr.assume_init())?;
+            let out_len = out_len.assume_init();
+            Ok(std::slice::from_raw_parts(*out_ptr, out_len).into())
+        }
+    }
 }
 
 impl Drop for LcCBB {
@@ -83,6 +83,83 @@
 //! # }
 //! ```
 //!
+//! ### AES-256 XTS
+//!
+//! XTS mode is intended for encrypting fixed-size storage sectors. Each sector is encrypted
+//! with a tweak derived from its sector number, so the same key can be used for every sector.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::{
+//!     DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext, UnboundCipherKey,
+//!     AES_256_XTS,
+//! };
+//!
+//! let original_sector = [0x5au8; 512];
+//! let mut in_out_buffer = original_sector;
+//!
+//! // The two 256-bit halves of the key must differ.
+//! let mut key_bytes = [0x11u8; 64];
+//! key_bytes[32..].fill(0x22);
+//!
+//! let key = UnboundCipherKey::new(&AES_256_XTS, &key_bytes)?;
+//! let encrypting_key = EncryptingKey::xts(key)?;
+//! encrypting_key.less_safe_encrypt(&mut in_out_buffer, EncryptionContext::xts_sector(7))?;
+//!
+//! let key = UnboundCipherKey::new(&AES_256_XTS, &key_bytes)?;
+//! let decrypting_key = DecryptingKey::xts(key)?;
+//! let plaintext = decrypting_key.decrypt(&mut in_out_buffer, DecryptionContext::xts_sector(7))?;
+//! assert_eq!(&original_sector[..], plaintext);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Streaming Encryption
+//!
+//! [`StreamingEncryptingKey`] and [`StreamingDecryptingKey`] process data incrementally, for
+//! inputs that are too large to hold in a single buffer. The output is identical to that of the
+//! one-shot keys.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::{
+//!     StreamingDecryptingKey, StreamingEncryptingKey, UnboundCipherKey, AES_128,
+//! };
+//!
+//! let key_bytes: &[u8] = &[
+//!     0xff, 0x0b, 0xe5, 0x84, 0x64, 0x0b, 0x00, 0xc8, 0x90, 0x7a, 0x4b, 0xbf, 0x82, 0x7c, 0xb6,
+//!     0xd1,
+//! ];
+//!
+//! let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+//! let mut encrypting_key = StreamingEncryptingKey::cbc_pkcs7(key)?;
+//! let mut ciphertext = Vec::new();
+//! let mut buffer = [0u8; 64 + 15];
+//! for chunk in [&b"This is a "[..], &b"secret message!"[..]] {
+//!     let update = encrypting_key.update(chunk, &mut buffer)?;
+//!     ciphertext.extend_from_slice(update.written());
+//! }
+//! let (context, update) = encrypting_key.finish(&mut buffer)?;
+//! ciphertext.extend_from_slice(update.written());
+//!
+//! let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+//! let mut decrypting_key = StreamingDecryptingKey::cbc_pkcs7(key, context)?;
+//! let mut plaintext = Vec::new();
+//! let update = decrypting_key.update(&ciphertext, &mut buffer)?;
+//! plaintext.extend_from_slice(update.written());
+//! let update = decrypting_key.finish(&mut buffer)?;
+//! plaintext.extend_from_slice(update.written());
+//! assert_eq!(b"This is a secret message!", plaintext.as_slice());
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## Constructing a `DecryptionContext` for decryption.
 //!
 //! ```rust
@@ -141,15 +218,25 @@ pub(crate) mod aes;
 pub(crate) mod block;
 pub(crate) mod chacha;
 pub(crate) mod key;
+mod streaming;
 
 use crate::error::Unspecified;
 use crate::hkdf;
 use crate::hkdf::KeyType;
 use crate::iv::{FixedLength, IV_LEN_128_BIT};
-use aws_lc::{AES_cbc_encrypt, AES_ctr128_encrypt, AES_DECRYPT, AES_ENCRYPT, AES_KEY};
+use crate::ptr::LcPtr;
+use aws_lc::{
+    AES_cbc_encrypt, AES_cfb128_encrypt, AES_ctr128_encrypt, AES_ecb_encrypt, AES_ofb128_encrypt,
+    EVP_CIPHER_CTX_new, EVP_DecryptInit_ex, EVP_DecryptUpdate, EVP_EncryptInit_ex,
+    EVP_EncryptUpdate, EVP_aes_256_xts, AES_DECRYPT, AES_ENCRYPT, AES_KEY,
+};
+use block::Block;
 use key::SymmetricCipherKey;
 use std::fmt::Debug;
 use std::mem::MaybeUninit;
+use std::os::raw::c_int;
+use std::ptr::null_mut;
+pub use streaming::{BufferUpdate, StreamingDecryptingKey, StreamingEncryptingKey};
 use zeroize::Zeroize;
 
 /// The cipher block padding strategy.
@@ -215,14 +302,28 @@ pub const AES_128_KEY_LEN: usize = 16;
 /// The number of bytes in an AES 256-bit key
 pub const AES_256_KEY_LEN: usize = 32;
 
-const MAX_CIPHER_KEY_LEN: usize = AES_256_KEY_LEN;
+/// The number of bytes in an AES-256 XTS key, which consists of two AES 256-bit keys.
+pub const AES_256_XTS_KEY_LEN: usize = 64;
+
+const MAX_CIPHER_KEY_LEN: usize = AES_256_XTS_KEY_LEN;
 
 /// The number of bytes for an AES-CBC initialization vector (IV)
 pub const AES_CBC_IV_LEN: usize = 16;
 
 /// The number of bytes for an AES-CTR initialization vector (IV)
 pub const AES_CTR_IV_LEN: usize = 16;
-const AES_BLOCK_LEN: usize = 16;
+
+/// The number of bytes for an AES-CFB128 initialization vector (IV)
+pub const AES_CFB128_IV_LEN: usize = 16;
+
+/// The number of bytes for an AES-OFB initialization vector (IV)
+pub const AES_OFB_IV_LEN: usize = 16;
+
+/// The number of bytes for an AES-XTS tweak
+pub const AES_XTS_TWEAK_LEN: usize = 16;
+
+/// The number of bytes in an AES block
+pub const AES_BLOCK_LEN: usize = 16;
 
 const MAX_CIPHER_BLOCK_LEN: usize = AES_BLOCK_LEN;
 
@@ -235,6 +336,20 @@ pub enum OperatingMode {
 
     /// Counter (CTR) mode.
     CTR,
+
+    /// 128-bit cipher feedback (CFB128) mode.
+    CFB128,
+
+    /// Output feedback (OFB) mode.
+    OFB,
+
+    /// XEX-based tweaked-codebook mode with ciphertext stealing (XTS), as specified in
+    /// IEEE 1619 and NIST SP 800-38E.
+    XTS,
+
+    /// Electronic codebook (ECB) mode. Each block is encrypted independently, so identical
+    /// plaintext blocks produce identical ciphertext blocks.
+    ECB,
 }
 
 macro_rules! define_cipher_context {
@@ -244,6 +359,22 @@ macro_rules! define_cipher_context {
         pub enum $name {
             /// A 128-bit Initialization Vector.
             Iv128(FixedLength<IV_LEN_128_BIT>),
+
+            /// A 128-bit XTS tweak.
+            Tweak128(FixedLength<AES_XTS_TWEAK_LEN>),
+
+            /// No contextual data, for modes such as ECB that do not use any.
+            None,
+        }
+
+        impl $name {
+            /// Constructs the XTS tweak for the sector (data unit) with the given number. As
+            /// specified in IEEE 1619, the tweak is the sector number encoded as a 128-bit
+            /// little-endian integer.
+            #[must_use]
+            pub fn xts_sector(sector: u128) -> Self {
+                $name::Tweak128(FixedLength::from(sector.to_le_bytes()))
+            }
         }
 
         impl<'a> TryFrom<&'a $name> for &'a [u8] {
@@ -252,6 +383,8 @@ macro_rules! define_cipher_context {
             fn try_from(value: &'a $name) -> Result<Self, Unspecified> {
                 match value {
                     $name::Iv128(iv) => Ok(iv.as_ref()),
+                    $name::Tweak128(tweak) => Ok(tweak.as_ref()),
+                    $name::None => Err(Unspecified),
                 }
             }
         }
@@ -260,6 +393,8 @@ macro_rules! define_cipher_context {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self {
                     Self::Iv128(_) => write!(f, "Iv128"),
+                    Self::Tweak128(_) => write!(f, "Tweak128"),
+                    Self::None => write!(f, "None"),
                 }
             }
         }
@@ -268,6 +403,8 @@ macro_rules! define_cipher_context {
             fn from(value: $other) -> Self {
                 match value {
                     $other::Iv128(iv) => $name::Iv128(iv),
+                    $other::Tweak128(tweak) => $name::Tweak128(tweak),
+                    $other::None => $name::None,
                 }
             }
         }
@@ -286,6 +423,9 @@ pub enum AlgorithmId {
 
     /// AES 256-bit
     Aes256,
+
+    /// AES 256-bit in XTS mode, using a double-length key
+    Aes256Xts,
 }
 
 /// A cipher algorithm.
@@ -310,6 +450,14 @@ pub static AES_256: Algorithm = Algorithm {
     block_len: AES_BLOCK_LEN,
 };
 
+/// AES 256-bit cipher for XTS mode. Keys are [`AES_256_XTS_KEY_LEN`] bytes long: two AES
+/// 256-bit keys, which must differ.
+pub static AES_256_XTS: Algorithm = Algorithm {
+    id: AlgorithmId::Aes256Xts,
+    key_len: AES_256_XTS_KEY_LEN,
+    block_len: AES_BLOCK_LEN,
+};
+
 impl Algorithm {
     fn id(&self) -> &AlgorithmId {
         &self.id
@@ -319,37 +467,65 @@ impl Algorithm {
         self.block_len
     }
 
+    fn supports_mode(&self, mode: OperatingMode) -> bool {
+        match self.id {
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => {
+                matches!(
+                    mode,
+                    OperatingMode::CBC
+                        | OperatingMode::CTR
+                        | OperatingMode::CFB128
+                        | OperatingMode::OFB
+                        | OperatingMode::ECB
+                )
+            }
+            AlgorithmId::Aes256Xts => mode == OperatingMode::XTS,
+        }
+    }
+
     fn new_encryption_context(
         &self,
         mode: OperatingMode,
     ) -> Result<EncryptionContext, Unspecified> {
-        match self.id {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => match mode {
-                OperatingMode::CBC | OperatingMode::CTR => {
-                    Ok(EncryptionContext::Iv128(FixedLength::new()?))
-                }
-            },
+        if !self.supports_mode(mode) {
+            return Err(Unspecified);
+        }
+        match mode {
+            OperatingMode::CBC
+            | OperatingMode::CTR
+            | OperatingMode::CFB128
+            | OperatingMode::OFB => Ok(EncryptionContext::Iv128(FixedLength::new()?)),
+            OperatingMode::XTS => Ok(EncryptionContext::Tweak128(FixedLength::new()?)),
+            OperatingMode::ECB => Ok(EncryptionContext::None),
         }
     }
 
     fn is_valid_encryption_context(&self, mode: OperatingMode, input: &EncryptionContext) -> bool {
-        match self.id {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => match mode {
-                OperatingMode::CBC | OperatingMode::CTR => {
+        self.supports_mode(mode)
+            && match mode {
+                OperatingMode::CBC
+                | OperatingMode::CTR
+                | OperatingMode::CFB128
+                | OperatingMode::OFB => {
                     matches!(input, EncryptionContext::Iv128(_))
                 }
-            },
-        }
+                OperatingMode::XTS => matches!(input, EncryptionContext::Tweak128(_)),
+                OperatingMode::ECB => matches!(input, EncryptionContext::None),
+            }
     }
 
     fn is_valid_decryption_context(&self, mode: OperatingMode, input: &DecryptionContext) -> bool {
-        match self.id {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => match mode {
-                OperatingMode::CBC | OperatingMode::CTR => {
+        self.supports_mode(mode)
+            && match mode {
+                OperatingMode::CBC
+                | OperatingMode::CTR
+                | OperatingMode::CFB128
+                | OperatingMode::OFB => {
                     matches!(input, DecryptionContext::Iv128(_))
                 }
-            },
-        }
+                OperatingMode::XTS => matches!(input, DecryptionContext::Tweak128(_)),
+                OperatingMode::ECB => matches!(input, DecryptionContext::None),
+            }
     }
 }
 
@@ -390,11 +566,12 @@ impl UnboundCipherKey {
     /// # Errors
     ///
     /// * [`Unspecified`] if `key_bytes.len()` does not match the
-    /// length required by `algorithm`.
+    /// length required by `algorithm`, or if the two halves of an [`AES_256_XTS`] key are equal.
     pub fn new(algorithm: &'static Algorithm, key_bytes: &[u8]) -> Result<Self, Unspecified> {
         let key = match algorithm.id() {
             AlgorithmId::Aes128 => SymmetricCipherKey::aes128(key_bytes),
             AlgorithmId::Aes256 => SymmetricCipherKey::aes256(key_bytes),
+            AlgorithmId::Aes256Xts => SymmetricCipherKey::aes256_xts(key_bytes),
         }?;
         Ok(UnboundCipherKey { algorithm, key })
     }
@@ -405,6 +582,25 @@ impl UnboundCipherKey {
     pub fn algorithm(&self) -> &'static Algorithm {
         self.algorithm
     }
+
+    /// Encrypts a single block in place with the raw block cipher, without any mode of
+    /// operation. This is a building block for other constructions, such as CMAC.
+    ///
+    /// The AES implementations in AWS-LC do not use secret-dependent memory accesses or
+    /// branches, so the running time does not depend on the key or the block.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key is not an [`AES_128`] or [`AES_256`] key.
+    pub fn encrypt_block(&self, block: &mut [u8; AES_BLOCK_LEN]) -> Result<(), Unspecified> {
+        match self.algorithm.id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => {
+                let encrypted = self.key.encrypt_block(Block::from(&*block));
+                block.copy_from_slice(encrypted.as_ref());
+                Ok(())
+            }
+            AlgorithmId::Aes256Xts => Err(Unspecified),
+        }
+    }
 }
 
 /// A cipher encryption key that performs block padding.
@@ -424,12 +620,14 @@ impl PaddedBlockEncryptingKey {
         PaddedBlockEncryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::PKCS7)
     }
 
-    #[allow(clippy::unnecessary_wraps)]
     fn new(
         key: UnboundCipherKey,
         mode: OperatingMode,
         padding: PaddingStrategy,
     ) -> Result<PaddedBlockEncryptingKey, Unspecified> {
+        if !key.algorithm().supports_mode(mode) {
+            return Err(Unspecified);
+        }
         Ok(PaddedBlockEncryptingKey { key, mode, padding })
     }
 
@@ -512,12 +710,14 @@ impl PaddedBlockDecryptingKey {
         PaddedBlockDecryptingKey::new(key, OperatingMode::CBC, PaddingStrategy::PKCS7)
     }
 
-    #[allow(clippy::unnecessary_wraps)]
     fn new(
         key: UnboundCipherKey,
         mode: OperatingMode,
         padding: PaddingStrategy,
     ) -> Result<PaddedBlockDecryptingKey, Unspecified> {
+        if !key.algorithm().supports_mode(mode) {
+            return Err(Unspecified);
+        }
         Ok(PaddedBlockDecryptingKey { key, mode, padding })
     }
 
@@ -584,8 +784,62 @@ impl EncryptingKey {
         EncryptingKey::new(key, OperatingMode::CTR)
     }
 
-    #[allow(clippy::unnecessary_wraps)]
+    /// Constructs an `EncryptingKey` operating in cipher block chaining (CBC) mode, without
+    /// padding, using the provided key. The input must be a multiple of the block length.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cbc(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CBC)
+    }
+
+    /// Constructs an `EncryptingKey` operating in electronic codebook (ECB) mode using the
+    /// provided key. The input must be a multiple of the block length, and the context is
+    /// always [`EncryptionContext::None`].
+    ///
+    /// ECB mode leaks which plaintext blocks are equal. It is only intended for building other
+    /// constructions and for interoperability with existing formats.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn ecb(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::ECB)
+    }
+
+    /// Constructs an `EncryptingKey` operating in 128-bit cipher feedback (CFB128) mode using
+    /// the provided key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cfb128(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CFB128)
+    }
+
+    /// Constructs an `EncryptingKey` operating in output feedback (OFB) mode using the
+    /// provided key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn ofb(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::OFB)
+    }
+
+    /// Constructs an `EncryptingKey` operating in XTS mode using the provided [`AES_256_XTS`] key.
+    ///
+    /// Each call to `less_safe_encrypt` encrypts a single sector (data unit), which must be at
+    /// least one block long, using a [`EncryptionContext::Tweak128`] context, such as one
+    /// constructed with [`EncryptionContext::xts_sector`].
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key is not an [`AES_256_XTS`] key.
+    pub fn xts(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::XTS)
+    }
+
     fn new(key: UnboundCipherKey, mode: OperatingMode) -> Result<EncryptingKey, Unspecified> {
+        if !key.algorithm().supports_mode(mode) {
+            return Err(Unspecified);
+        }
         Ok(EncryptingKey { key, mode })
     }
 
@@ -658,8 +912,56 @@ impl DecryptingKey {
         DecryptingKey::new(key, OperatingMode::CTR)
     }
 
-    #[allow(clippy::unnecessary_wraps)]
+    /// Constructs a cipher decrypting key operating in cipher block chaining (CBC) mode,
+    /// without padding, using the provided key. The input must be a multiple of the block length.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn cbc(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CBC)
+    }
+
+    /// Constructs a cipher decrypting key operating in electronic codebook (ECB) mode using the
+    /// provided key. The input must be a multiple of the block length, and the context is
+    /// always [`DecryptionContext::None`].
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn ecb(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::ECB)
+    }
+
+    /// Constructs a cipher decrypting key operating in 128-bit cipher feedback (CFB128) mode
+    /// using the provided key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn cfb128(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CFB128)
+    }
+
+    /// Constructs a cipher decrypting key operating in output feedback (OFB) mode using the
+    /// provided key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn ofb(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::OFB)
+    }
+
+    /// Constructs a cipher decrypting key operating in XTS mode using the provided
+    /// [`AES_256_XTS`] key.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key is not an [`AES_256_XTS`] key.
+    pub fn xts(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::XTS)
+    }
+
     fn new(key: UnboundCipherKey, mode: OperatingMode) -> Result<DecryptingKey, Unspecified> {
+        if !key.algorithm().supports_mode(mode) {
+            return Err(Unspecified);
+        }
         Ok(DecryptingKey { key, mode })
     }
 
@@ -686,6 +988,13 @@ impl DecryptingKey {
         in_out: &'in_out mut [u8],
         context: DecryptionContext,
     ) -> Result<&'in_out mut [u8], Unspecified> {
+        if !self
+            .key
+            .algorithm()
+            .is_valid_decryption_context(self.mode, &context)
+        {
+            return Err(Unspecified);
+        }
         decrypt(&self.key, self.mode, in_out, context)
     }
 }
@@ -708,8 +1017,13 @@ fn encrypt(
     let block_len = key.algorithm().block_len();
 
     match mode {
-        OperatingMode::CTR => {}
-        _ => {
+        OperatingMode::CTR | OperatingMode::CFB128 | OperatingMode::OFB => {}
+        OperatingMode::XTS => {
+            if in_out.len() < block_len {
+                return Err(Unspecified);
+            }
+        }
+        OperatingMode::CBC | OperatingMode::ECB => {
             if (in_out.len() % block_len) != 0 {
                 return Err(Unspecified);
             }
@@ -719,9 +1033,29 @@ fn encrypt(
     match mode {
         OperatingMode::CBC => match key.algorithm().id() {
             AlgorithmId::Aes128 | AlgorithmId::Aes256 => encrypt_aes_cbc_mode(key, context, in_out),
+            AlgorithmId::Aes256Xts => Err(Unspecified),
         },
         OperatingMode::CTR => match key.algorithm().id() {
             AlgorithmId::Aes128 | AlgorithmId::Aes256 => encrypt_aes_ctr_mode(key, context, in_out),
+            AlgorithmId::Aes256Xts => Err(Unspecified),
+        },
+        OperatingMode::CFB128 => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => {
+                encrypt_aes_cfb128_mode(key, context, in_out)
+            }
+            AlgorithmId::Aes256Xts => Err(Unspecified),
+        },
+        OperatingMode::OFB => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => encrypt_aes_ofb_mode(key, context, in_out),
+            AlgorithmId::Aes256Xts => Err(Unspecified),
+        },
+        OperatingMode::XTS => match key.algorithm().id() {
+            AlgorithmId::Aes256Xts => encrypt_aes_xts_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => Err(Unspecified),
+        },
+        OperatingMode::ECB => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => encrypt_aes_ecb_mode(key, context, in_out),
+            AlgorithmId::Aes256Xts => Err(Unspecified),
         },
     }
 }
@@ -735,8 +1069,13 @@ fn decrypt<'in_out>(
     let block_len = key.algorithm().block_len();
 
     match mode {
-        OperatingMode::CTR => {}
-        _ => {
+        OperatingMode::CTR | OperatingMode::CFB128 | OperatingMode::OFB => {}
+        OperatingMode::XTS => {
+            if in_out.len() < block_len {
+                return Err(Unspecified);
+            }
+        }
+        OperatingMode::CBC | OperatingMode::ECB => {
             if (in_out.len() % block_len) != 0 {
                 return Err(Unspecified);
             }
@@ -746,9 +1085,29 @@ fn decrypt<'in_out>(
     match mode {
         OperatingMode::CBC => match key.algorithm().id() {
             AlgorithmId::Aes128 | AlgorithmId::Aes256 => decrypt_aes_cbc_mode(key, context, in_out),
+            AlgorithmId::Aes256Xts => Err(Unspecified),
         },
         OperatingMode::CTR => match key.algorithm().id() {
             AlgorithmId::Aes128 | AlgorithmId::Aes256 => decrypt_aes_ctr_mode(key, context, in_out),
+            AlgorithmId::Aes256Xts => Err(Unspecified),
+        },
+        OperatingMode::CFB128 => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => {
+                decrypt_aes_cfb128_mode(key, context, in_out)
+            }
+            AlgorithmId::Aes256Xts => Err(Unspecified),
+        },
+        OperatingMode::OFB => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => decrypt_aes_ofb_mode(key, context, in_out),
+            AlgorithmId::Aes256Xts => Err(Unspecified),
+        },
+        OperatingMode::XTS => match key.algorithm().id() {
+            AlgorithmId::Aes256Xts => decrypt_aes_xts_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => Err(Unspecified),
+        },
+        OperatingMode::ECB => match key.algorithm().id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes256 => decrypt_aes_ecb_mode(key, context, in_out),
+            AlgorithmId::Aes256Xts => Err(Unspecified),
         },
     }
 }
@@ -789,6 +1148,132 @@ fn decrypt_aes_ctr_mode<'in_out>(
     encrypt_aes_ctr_mode(key, context.into(), in_out).map(|_| in_out)
 }
 
+#[allow(clippy::needless_pass_by_value)]
+fn encrypt_aes_ecb_mode(
+    key: &UnboundCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match (&key.key, &context) {
+        (
+            SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. },
+            EncryptionContext::None,
+        ) => enc_key,
+        _ => return Err(Unspecified),
+    };
+
+    aes_ecb(key, in_out, AES_ENCRYPT);
+
+    Ok(DecryptionContext::None)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn decrypt_aes_ecb_mode<'in_out>(
+    key: &UnboundCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match (&key.key, &context) {
+        (
+            SymmetricCipherKey::Aes128 { dec_key, .. } | SymmetricCipherKey::Aes256 { dec_key, .. },
+            DecryptionContext::None,
+        ) => dec_key,
+        _ => return Err(Unspecified),
+    };
+
+    aes_ecb(key, in_out, AES_DECRYPT);
+
+    Ok(in_out)
+}
+
+fn encrypt_aes_cfb128_mode(
+    key: &UnboundCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key.key {
+        SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
+            enc_key
+        }
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = {
+        let mut iv = [0u8; AES_CFB128_IV_LEN];
+        iv.copy_from_slice((&context).try_into()?);
+        iv
+    };
+
+    aes_cfb128_encrypt(key, &mut iv, in_out, AES_ENCRYPT);
+    iv.zeroize();
+
+    Ok(context.into())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn decrypt_aes_cfb128_mode<'in_out>(
+    key: &UnboundCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    // CFB uses the block cipher in the encrypt direction for decryption as well.
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key.key {
+        SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
+            enc_key
+        }
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = {
+        let mut iv = [0u8; AES_CFB128_IV_LEN];
+        iv.copy_from_slice((&context).try_into()?);
+        iv
+    };
+
+    aes_cfb128_encrypt(key, &mut iv, in_out, AES_DECRYPT);
+    iv.zeroize();
+
+    Ok(in_out)
+}
+
+fn encrypt_aes_ofb_mode(
+    key: &UnboundCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key.key {
+        SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
+            enc_key
+        }
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = {
+        let mut iv = [0u8; AES_OFB_IV_LEN];
+        iv.copy_from_slice((&context).try_into()?);
+        iv
+    };
+
+    aes_ofb128_encrypt(key, &mut iv, in_out);
+    iv.zeroize();
+
+    Ok(context.into())
+}
+
+fn decrypt_aes_ofb_mode<'in_out>(
+    key: &UnboundCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    // it's the same in OFB, just providing a nice named wrapper to match
+    encrypt_aes_ofb_mode(key, context.into(), in_out).map(|_| in_out)
+}
+
 fn encrypt_aes_cbc_mode(
     key: &UnboundCipherKey,
     context: EncryptionContext,
@@ -840,6 +1325,77 @@ fn decrypt_aes_cbc_mode<'in_out>(
     Ok(in_out)
 }
 
+fn encrypt_aes_xts_mode(
+    key: &UnboundCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    match (&key.key, &context) {
+        (SymmetricCipherKey::Aes256Xts { raw_key }, EncryptionContext::Tweak128(tweak)) => {
+            aes_xts_cipher(raw_key, tweak.as_ref(), in_out, true)?;
+        }
+        _ => return Err(Unspecified),
+    }
+
+    Ok(context.into())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn decrypt_aes_xts_mode<'in_out>(
+    key: &UnboundCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    match (&key.key, &context) {
+        (SymmetricCipherKey::Aes256Xts { raw_key }, DecryptionContext::Tweak128(tweak)) => {
+            aes_xts_cipher(raw_key, tweak.as_ref(), in_out, false)?;
+        }
+        _ => return Err(Unspecified),
+    }
+
+    Ok(in_out)
+}
+
+fn aes_xts_cipher(
+    raw_key: &[u8],
+    tweak: &[u8],
+    in_out: &mut [u8],
+    encrypt: bool,
+) -> Result<(), Unspecified> {
+    let in_len = c_int::try_from(in_out.len()).map_err(|_| Unspecified)?;
+    let ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+    let cipher = unsafe { EVP_aes_256_xts() };
+    let mut out_len: c_int = 0;
+
+    // `in_out` is processed as a single data unit, so it must be passed in one update call.
+    let success = unsafe {
+        if encrypt {
+            1 == EVP_EncryptInit_ex(*ctx, cipher, null_mut(), raw_key.as_ptr(), tweak.as_ptr())
+                && 1 == EVP_EncryptUpdate(
+                    *ctx,
+                    in_out.as_mut_ptr(),
+                    &mut out_len,
+                    in_out.as_ptr(),
+                    in_len,
+                )
+        } else {
+            1 == EVP_DecryptInit_ex(*ctx, cipher, null_mut(), raw_key.as_ptr(), tweak.as_ptr())
+                && 1 == EVP_DecryptUpdate(
+                    *ctx,
+                    in_out.as_mut_ptr(),
+                    &mut out_len,
+                    in_out.as_ptr(),
+                    in_len,
+                )
+        }
+    };
+
+    if !success || out_len != in_len {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
 fn aes_ctr128_encrypt(key: &AES_KEY, iv: &mut [u8], block_buffer: &mut [u8], in_out: &mut [u8]) {
     let mut num = MaybeUninit::<u32>::new(0);
 
@@ -858,6 +1414,45 @@ fn aes_ctr128_encrypt(key: &AES_KEY, iv: &mut [u8], block_buffer: &mut [u8], in_
     Zeroize::zeroize(block_buffer);
 }
 
+fn aes_ecb(key: &AES_KEY, in_out: &mut [u8], enc: c_int) {
+    for block in in_out.chunks_exact_mut(AES_BLOCK_LEN) {
+        unsafe {
+            AES_ecb_encrypt(block.as_ptr(), block.as_mut_ptr(), key, enc);
+        }
+    }
+}
+
+fn aes_cfb128_encrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8], enc: c_int) {
+    let mut num: c_int = 0;
+
+    unsafe {
+        AES_cfb128_encrypt(
+            in_out.as_ptr(),
+            in_out.as_mut_ptr(),
+            in_out.len(),
+            key,
+            iv.as_mut_ptr(),
+            &mut num,
+            enc,
+        );
+    }
+}
+
+fn aes_ofb128_encrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8]) {
+    let mut num: c_int = 0;
+
+    unsafe {
+        AES_ofb128_encrypt(
+            in_out.as_ptr(),
+            in_out.as_mut_ptr(),
+            in_out.len(),
+            key,
+            iv.as_mut_ptr(),
+            &mut num,
+        );
+    }
+}
+
 fn aes_cbc_encrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8]) {
     unsafe {
         AES_cbc_encrypt(
@@ -1043,6 +1638,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_aes_128_cfb128() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_128, OperatingMode::CFB128, i);
+        }
+    }
+
+    #[test]
+    fn test_aes_256_cfb128() {
+        let key =
+            from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_256, OperatingMode::CFB128, i);
+        }
+    }
+
+    #[test]
+    fn test_aes_128_ofb() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_128, OperatingMode::OFB, i);
+        }
+    }
+
+    #[test]
+    fn test_aes_256_ofb() {
+        let key =
+            from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap();
+        for i in 0..=50 {
+            helper_test_cipher_n_bytes(key.as_slice(), &AES_256, OperatingMode::OFB, i);
+        }
+    }
+
     macro_rules! padded_cipher_kat {
         ($name:ident, $alg:expr, $mode:expr, $padding:expr, $key:literal, $iv: literal, $plaintext:literal, $ciphertext:literal) => {
             #[test]
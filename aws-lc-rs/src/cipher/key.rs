@@ -4,7 +4,8 @@
 use crate::cipher::aes::encrypt_block_aes;
 use crate::cipher::block::Block;
 use crate::cipher::chacha::ChaCha20Key;
-use crate::cipher::{AES_128_KEY_LEN, AES_256_KEY_LEN};
+use crate::cipher::{AES_128_KEY_LEN, AES_256_KEY_LEN, AES_256_XTS_KEY_LEN};
+use crate::constant_time;
 use crate::error::Unspecified;
 use aws_lc::{AES_set_decrypt_key, AES_set_encrypt_key, AES_KEY};
 use core::ptr::copy_nonoverlapping;
@@ -16,6 +17,7 @@ pub(crate) enum SymmetricCipherKey {
     Aes128 { enc_key: AES_KEY, dec_key: AES_KEY },
     Aes256 { enc_key: AES_KEY, dec_key: AES_KEY },
     ChaCha20 { raw_key: ChaCha20Key },
+    Aes256Xts { raw_key: [u8; AES_256_XTS_KEY_LEN] },
 }
 
 unsafe impl Send for SymmetricCipherKey {}
@@ -40,6 +42,7 @@ impl Drop for SymmetricCipherKey {
                     .unwrap();
                 dec_bytes.zeroize();
             },
+            SymmetricCipherKey::Aes256Xts { raw_key } => raw_key.zeroize(),
             SymmetricCipherKey::ChaCha20 { .. } => {}
         }
     }
@@ -126,13 +129,28 @@ impl SymmetricCipherKey {
         }
     }
 
-    #[allow(dead_code)]
+    pub(crate) fn aes256_xts(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if key_bytes.len() != AES_256_XTS_KEY_LEN {
+            return Err(Unspecified);
+        }
+        // IEEE 1619 requires the two halves of an XTS key to be different.
+        let (key1, key2) = key_bytes.split_at(AES_256_KEY_LEN);
+        if constant_time::verify_slices_are_equal(key1, key2).is_ok() {
+            return Err(Unspecified);
+        }
+        let mut raw_key = [0u8; AES_256_XTS_KEY_LEN];
+        raw_key.copy_from_slice(key_bytes);
+        Ok(SymmetricCipherKey::Aes256Xts { raw_key })
+    }
+
     #[inline]
     pub(crate) fn encrypt_block(&self, block: Block) -> Block {
         match self {
             SymmetricCipherKey::Aes128 { enc_key, .. }
             | SymmetricCipherKey::Aes256 { enc_key, .. } => encrypt_block_aes(enc_key, block),
-            SymmetricCipherKey::ChaCha20 { .. } => panic!("Unsupported algorithm!"),
+            SymmetricCipherKey::ChaCha20 { .. } | SymmetricCipherKey::Aes256Xts { .. } => {
+                panic!("Unsupported algorithm!")
+            }
         }
     }
 }
@@ -0,0 +1,679 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::cipher::key::SymmetricCipherKey;
+use crate::cipher::{
+    Algorithm, DecryptionContext, EncryptionContext, OperatingMode, PaddingStrategy,
+    UnboundCipherKey, AES_BLOCK_LEN,
+};
+use crate::error::Unspecified;
+use aws_lc::{AES_cbc_encrypt, AES_ctr128_encrypt, AES_DECRYPT, AES_ENCRYPT, AES_KEY};
+use std::fmt::Debug;
+use std::os::raw::c_uint;
+use zeroize::Zeroize;
+
+/// The result of a streaming `update` or `finish` call: the part of the output buffer that
+/// was written, and the part that remains.
+pub struct BufferUpdate<'a> {
+    written: &'a [u8],
+    remainder: &'a mut [u8],
+}
+
+impl<'a> BufferUpdate<'a> {
+    fn new(output: &'a mut [u8], written_len: usize) -> Self {
+        let (written, remainder) = output.split_at_mut(written_len);
+        Self { written, remainder }
+    }
+
+    /// Returns the bytes that were written to the output buffer.
+    #[must_use]
+    pub fn written(&self) -> &[u8] {
+        self.written
+    }
+
+    /// Returns the unused part of the output buffer, which can be passed to the next call.
+    #[must_use]
+    pub fn remainder(&self) -> &[u8] {
+        self.remainder
+    }
+
+    /// Returns the unused part of the output buffer, which can be passed to the next call.
+    #[must_use]
+    pub fn remainder_mut(&mut self) -> &mut [u8] {
+        self.remainder
+    }
+}
+
+impl Debug for BufferUpdate<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferUpdate")
+            .field("written", &self.written.len())
+            .field("remainder", &self.remainder.len())
+            .finish()
+    }
+}
+
+/// The chaining state that is carried from one call to the next.
+enum StreamingState {
+    /// The current CBC chaining value, and the bytes of an incomplete block.
+    Cbc {
+        iv: [u8; AES_BLOCK_LEN],
+        buffer: [u8; AES_BLOCK_LEN],
+        buffered: usize,
+    },
+    /// The current counter block, the key stream of the current block, and the number of key
+    /// stream bytes of that block that have been used.
+    Ctr {
+        iv: [u8; AES_BLOCK_LEN],
+        key_stream: [u8; AES_BLOCK_LEN],
+        used: c_uint,
+    },
+}
+
+impl StreamingState {
+    fn new(mode: OperatingMode, iv: &[u8]) -> Result<Self, Unspecified> {
+        let iv: [u8; AES_BLOCK_LEN] = iv.try_into().map_err(|_| Unspecified)?;
+        match mode {
+            OperatingMode::CBC => Ok(StreamingState::Cbc {
+                iv,
+                buffer: [0u8; AES_BLOCK_LEN],
+                buffered: 0,
+            }),
+            OperatingMode::CTR => Ok(StreamingState::Ctr {
+                iv,
+                key_stream: [0u8; AES_BLOCK_LEN],
+                used: 0,
+            }),
+            _ => Err(Unspecified),
+        }
+    }
+
+    /// Processes `input` into `output`, returning the number of bytes written. In CBC mode only
+    /// whole blocks are processed, and incomplete blocks are buffered until more input arrives.
+    /// When `hold_back` is set, the last block is always buffered, even when it is complete,
+    /// since it may be the padding block.
+    fn update(
+        &mut self,
+        key: &AES_KEY,
+        enc: i32,
+        hold_back: bool,
+        mut input: &[u8],
+        output: &mut [u8],
+    ) -> usize {
+        match self {
+            StreamingState::Cbc {
+                iv,
+                buffer,
+                buffered,
+            } => {
+                let mut written = 0;
+
+                let take = input.len().min(AES_BLOCK_LEN - *buffered);
+                buffer[*buffered..*buffered + take].copy_from_slice(&input[..take]);
+                *buffered += take;
+                input = &input[take..];
+                if *buffered < AES_BLOCK_LEN || (hold_back && input.is_empty()) {
+                    return written;
+                }
+
+                aes_cbc(key, iv, enc, buffer, &mut output[..AES_BLOCK_LEN]);
+                written += AES_BLOCK_LEN;
+
+                let mut blocks = input.len() / AES_BLOCK_LEN;
+                if hold_back && blocks > 0 && input.len() % AES_BLOCK_LEN == 0 {
+                    blocks -= 1;
+                }
+                let bulk_len = blocks * AES_BLOCK_LEN;
+                aes_cbc(
+                    key,
+                    iv,
+                    enc,
+                    &input[..bulk_len],
+                    &mut output[written..written + bulk_len],
+                );
+                written += bulk_len;
+                input = &input[bulk_len..];
+
+                buffer[..input.len()].copy_from_slice(input);
+                *buffered = input.len();
+
+                written
+            }
+            StreamingState::Ctr {
+                iv,
+                key_stream,
+                used,
+            } => {
+                unsafe {
+                    AES_ctr128_encrypt(
+                        input.as_ptr(),
+                        output.as_mut_ptr(),
+                        input.len(),
+                        key,
+                        iv.as_mut_ptr(),
+                        key_stream.as_mut_ptr(),
+                        used,
+                    );
+                }
+                input.len()
+            }
+        }
+    }
+}
+
+impl Drop for StreamingState {
+    fn drop(&mut self) {
+        match self {
+            StreamingState::Cbc { iv, buffer, .. } => {
+                iv.zeroize();
+                buffer.zeroize();
+            }
+            StreamingState::Ctr { iv, key_stream, .. } => {
+                iv.zeroize();
+                key_stream.zeroize();
+            }
+        }
+    }
+}
+
+fn aes_cbc(key: &AES_KEY, iv: &mut [u8; AES_BLOCK_LEN], enc: i32, input: &[u8], output: &mut [u8]) {
+    debug_assert_eq!(input.len(), output.len());
+    unsafe {
+        AES_cbc_encrypt(
+            input.as_ptr(),
+            output.as_mut_ptr(),
+            input.len(),
+            key,
+            iv.as_mut_ptr(),
+            enc,
+        );
+    }
+}
+
+fn aes_key(key: &UnboundCipherKey, mode: OperatingMode, enc: i32) -> Result<&AES_KEY, Unspecified> {
+    // CTR mode uses the block cipher in the encrypt direction for decryption as well.
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    match &key.key {
+        SymmetricCipherKey::Aes128 { enc_key, dec_key }
+        | SymmetricCipherKey::Aes256 { enc_key, dec_key } => {
+            if mode == OperatingMode::CBC && enc == AES_DECRYPT {
+                Ok(dec_key)
+            } else {
+                Ok(enc_key)
+            }
+        }
+        _ => Err(Unspecified),
+    }
+}
+
+/// The minimum output buffer length for an `update` call with an input of `input_len` bytes.
+fn min_update_output_len(
+    mode: OperatingMode,
+    algorithm: &Algorithm,
+    input_len: usize,
+) -> Option<usize> {
+    match mode {
+        OperatingMode::CBC => input_len.checked_add(algorithm.block_len() - 1),
+        _ => Some(input_len),
+    }
+}
+
+/// A key for encrypting data incrementally, in CBC mode with PKCS#7 padding or in CTR mode.
+///
+/// Data is passed to [`update`](Self::update) in as many pieces as needed, and
+/// [`finish`](Self::finish) is called once at the end. The ciphertext is identical to that
+/// produced by [`PaddedBlockEncryptingKey`](super::PaddedBlockEncryptingKey) or
+/// [`EncryptingKey`](super::EncryptingKey) for the whole plaintext.
+pub struct StreamingEncryptingKey {
+    key: UnboundCipherKey,
+    mode: OperatingMode,
+    context: EncryptionContext,
+    state: StreamingState,
+}
+
+impl StreamingEncryptingKey {
+    /// Constructs a `StreamingEncryptingKey` operating in cipher block chaining (CBC) mode,
+    /// with PKCS#7 padding, using a randomly generated IV.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the key.
+    pub fn cbc_pkcs7(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        let context = key.algorithm().new_encryption_context(OperatingMode::CBC)?;
+        Self::less_safe_cbc_pkcs7(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` operating in cipher block chaining (CBC) mode,
+    /// with PKCS#7 padding, using the provided context.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for CBC mode.
+    pub fn less_safe_cbc_pkcs7(
+        key: UnboundCipherKey,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CBC, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` operating in counter (CTR) mode, using a randomly
+    /// generated IV.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the key.
+    pub fn ctr(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        let context = key.algorithm().new_encryption_context(OperatingMode::CTR)?;
+        Self::less_safe_ctr(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` operating in counter (CTR) mode, using the
+    /// provided context.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for CTR mode.
+    pub fn less_safe_ctr(
+        key: UnboundCipherKey,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CTR, context)
+    }
+
+    fn new(
+        key: UnboundCipherKey,
+        mode: OperatingMode,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        if !key.algorithm().is_valid_encryption_context(mode, &context) {
+            return Err(Unspecified);
+        }
+        aes_key(&key, mode, AES_ENCRYPT)?;
+        let state = StreamingState::new(mode, (&context).try_into()?)?;
+        Ok(Self {
+            key,
+            mode,
+            context,
+            state,
+        })
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm()
+    }
+
+    /// Returns the cipher operating mode.
+    #[must_use]
+    pub fn mode(&self) -> OperatingMode {
+        self.mode
+    }
+
+    /// Encrypts `input`, writing ciphertext to the start of `output`. In CBC mode, ciphertext is
+    /// only written for complete blocks, and the rest of the input is kept for the next call.
+    ///
+    /// `output` must be at least `input.len()` bytes long in CTR mode, and at least
+    /// `input.len() + block_len - 1` bytes long in CBC mode.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is too small.
+    pub fn update<'output>(
+        &mut self,
+        input: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<BufferUpdate<'output>, Unspecified> {
+        let min_output_len =
+            min_update_output_len(self.mode, self.algorithm(), input.len()).ok_or(Unspecified)?;
+        if output.len() < min_output_len {
+            return Err(Unspecified);
+        }
+        let key = aes_key(&self.key, self.mode, AES_ENCRYPT)?;
+        let written = self.state.update(key, AES_ENCRYPT, false, input, output);
+        Ok(BufferUpdate::new(output, written))
+    }
+
+    /// Completes the encryption, writing any remaining ciphertext to the start of `output`. In
+    /// CBC mode this is the final, padded block, so `output` must be at least one block long.
+    ///
+    /// Returns the context needed to decrypt the ciphertext.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is too small.
+    pub fn finish(
+        mut self,
+        output: &mut [u8],
+    ) -> Result<(DecryptionContext, BufferUpdate<'_>), Unspecified> {
+        let written = match &mut self.state {
+            StreamingState::Cbc {
+                iv,
+                buffer,
+                buffered,
+            } => {
+                if output.len() < AES_BLOCK_LEN {
+                    return Err(Unspecified);
+                }
+                // PKCS#7: pad with n bytes of value n, adding a whole block if none is
+                // incomplete.
+                #[allow(clippy::cast_possible_truncation)]
+                let padding = (AES_BLOCK_LEN - *buffered) as u8;
+                buffer[*buffered..].fill(padding);
+                let key = aes_key(&self.key, self.mode, AES_ENCRYPT)?;
+                aes_cbc(key, iv, AES_ENCRYPT, buffer, &mut output[..AES_BLOCK_LEN]);
+                *buffered = 0;
+                AES_BLOCK_LEN
+            }
+            StreamingState::Ctr { .. } => 0,
+        };
+        Ok((self.context.into(), BufferUpdate::new(output, written)))
+    }
+}
+
+impl Debug for StreamingEncryptingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingEncryptingKey")
+            .field("key", &self.key)
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A key for decrypting data incrementally, in CBC mode with PKCS#7 padding or in CTR mode.
+///
+/// Data is passed to [`update`](Self::update) in as many pieces as needed, and
+/// [`finish`](Self::finish) is called once at the end. The plaintext is identical to that
+/// produced by [`PaddedBlockDecryptingKey`](super::PaddedBlockDecryptingKey) or
+/// [`DecryptingKey`](super::DecryptingKey) for the whole ciphertext.
+///
+/// In CBC mode the padding can only be checked in `finish`, so plaintext returned by `update`
+/// must not be relied upon until `finish` succeeds.
+pub struct StreamingDecryptingKey {
+    key: UnboundCipherKey,
+    mode: OperatingMode,
+    state: StreamingState,
+}
+
+impl StreamingDecryptingKey {
+    /// Constructs a `StreamingDecryptingKey` operating in cipher block chaining (CBC) mode,
+    /// with PKCS#7 padding, using the provided context.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for CBC mode.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn cbc_pkcs7(
+        key: UnboundCipherKey,
+        context: DecryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CBC, &context)
+    }
+
+    /// Constructs a `StreamingDecryptingKey` operating in counter (CTR) mode, using the
+    /// provided context.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `context` is not valid for CTR mode.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn ctr(key: UnboundCipherKey, context: DecryptionContext) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CTR, &context)
+    }
+
+    fn new(
+        key: UnboundCipherKey,
+        mode: OperatingMode,
+        context: &DecryptionContext,
+    ) -> Result<Self, Unspecified> {
+        if !key.algorithm().is_valid_decryption_context(mode, context) {
+            return Err(Unspecified);
+        }
+        aes_key(&key, mode, AES_DECRYPT)?;
+        let state = StreamingState::new(mode, context.try_into()?)?;
+        Ok(Self { key, mode, state })
+    }
+
+    /// Returns the cipher algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &Algorithm {
+        self.key.algorithm()
+    }
+
+    /// Returns the cipher operating mode.
+    #[must_use]
+    pub fn mode(&self) -> OperatingMode {
+        self.mode
+    }
+
+    /// Decrypts `input`, writing plaintext to the start of `output`. In CBC mode, the last
+    /// block seen so far is always kept for the next call, since it may hold the padding.
+    ///
+    /// `output` must be at least `input.len()` bytes long in CTR mode, and at least
+    /// `input.len() + block_len - 1` bytes long in CBC mode.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is too small.
+    pub fn update<'output>(
+        &mut self,
+        input: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<BufferUpdate<'output>, Unspecified> {
+        let min_output_len =
+            min_update_output_len(self.mode, self.algorithm(), input.len()).ok_or(Unspecified)?;
+        if output.len() < min_output_len {
+            return Err(Unspecified);
+        }
+        let key = aes_key(&self.key, self.mode, AES_DECRYPT)?;
+        let written = self.state.update(key, AES_DECRYPT, true, input, output);
+        Ok(BufferUpdate::new(output, written))
+    }
+
+    /// Completes the decryption, writing any remaining plaintext to the start of `output`. In
+    /// CBC mode this is the final block with its padding removed, so `output` must be at least
+    /// one block long.
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if `output` is too small, if the ciphertext was not a
+    ///   multiple of the block length, or if the padding is invalid.
+    pub fn finish(mut self, output: &mut [u8]) -> Result<BufferUpdate<'_>, Unspecified> {
+        let written = match &mut self.state {
+            StreamingState::Cbc {
+                iv,
+                buffer,
+                buffered,
+            } => {
+                if output.len() < AES_BLOCK_LEN || *buffered != AES_BLOCK_LEN {
+                    return Err(Unspecified);
+                }
+                let key = aes_key(&self.key, self.mode, AES_DECRYPT)?;
+                let mut block = [0u8; AES_BLOCK_LEN];
+                aes_cbc(key, iv, AES_DECRYPT, buffer, &mut block);
+                let unpadded = PaddingStrategy::PKCS7
+                    .remove_padding(AES_BLOCK_LEN, &mut block)
+                    .map(|plaintext| {
+                        output[..plaintext.len()].copy_from_slice(plaintext);
+                        plaintext.len()
+                    });
+                block.zeroize();
+                *buffered = 0;
+                unpadded?
+            }
+            StreamingState::Ctr { .. } => 0,
+        };
+        Ok(BufferUpdate::new(output, written))
+    }
+}
+
+impl Debug for StreamingDecryptingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingDecryptingKey")
+            .field("key", &self.key)
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cipher::{
+        DecryptingKey, EncryptingKey, EncryptionContext, PaddedBlockDecryptingKey,
+        PaddedBlockEncryptingKey, StreamingDecryptingKey, StreamingEncryptingKey, UnboundCipherKey,
+        AES_128, AES_256,
+    };
+    use crate::iv::FixedLength;
+    use crate::test::from_hex;
+
+    const IV: [u8; 16] = [
+        0x8d, 0xdb, 0x7d, 0xf1, 0x56, 0xf5, 0x1c, 0xde, 0x63, 0xe3, 0x4a, 0x34, 0xb0, 0xdf, 0x28,
+        0xf0,
+    ];
+
+    fn context() -> EncryptionContext {
+        EncryptionContext::Iv128(FixedLength::from(IV))
+    }
+
+    fn encrypt_streaming(mut key: StreamingEncryptingKey, input: &[u8], step: usize) -> Vec<u8> {
+        let mut ciphertext = Vec::new();
+        let mut output = vec![0u8; step + 15];
+        for chunk in input.chunks(step) {
+            let update = key.update(chunk, &mut output).unwrap();
+            ciphertext.extend_from_slice(update.written());
+        }
+        let (_, update) = key.finish(&mut output).unwrap();
+        ciphertext.extend_from_slice(update.written());
+        ciphertext
+    }
+
+    fn decrypt_streaming(mut key: StreamingDecryptingKey, input: &[u8], step: usize) -> Vec<u8> {
+        let mut plaintext = Vec::new();
+        let mut output = vec![0u8; step + 15];
+        for chunk in input.chunks(step) {
+            let update = key.update(chunk, &mut output).unwrap();
+            plaintext.extend_from_slice(update.written());
+        }
+        let update = key.finish(&mut output).unwrap();
+        plaintext.extend_from_slice(update.written());
+        plaintext
+    }
+
+    #[test]
+    fn test_streaming_cbc_matches_one_shot() {
+        for (alg, key_bytes) in [
+            (
+                &AES_128,
+                from_hex("000102030405060708090a0b0c0d0e0f").unwrap(),
+            ),
+            (
+                &AES_256,
+                from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+                    .unwrap(),
+            ),
+        ] {
+            for len in [0, 1, 15, 16, 17, 31, 32, 33, 100] {
+                let plaintext: Vec<u8> = (0..len).map(|i| u8::try_from(i).unwrap()).collect();
+
+                let key = UnboundCipherKey::new(alg, &key_bytes).unwrap();
+                let mut expected = plaintext.clone();
+                PaddedBlockEncryptingKey::cbc_pkcs7(key)
+                    .unwrap()
+                    .less_safe_encrypt(&mut expected, context())
+                    .unwrap();
+
+                for step in [1, 3, 15, 16, 17, 64] {
+                    let key = UnboundCipherKey::new(alg, &key_bytes).unwrap();
+                    let key = StreamingEncryptingKey::less_safe_cbc_pkcs7(key, context()).unwrap();
+                    let ciphertext = encrypt_streaming(key, &plaintext, step);
+                    assert_eq!(expected, ciphertext);
+
+                    let key = UnboundCipherKey::new(alg, &key_bytes).unwrap();
+                    let key = StreamingDecryptingKey::cbc_pkcs7(key, context().into()).unwrap();
+                    assert_eq!(plaintext, decrypt_streaming(key, &ciphertext, step));
+                }
+
+                let key = UnboundCipherKey::new(alg, &key_bytes).unwrap();
+                let mut one_shot = expected.clone();
+                let decrypted = PaddedBlockDecryptingKey::cbc_pkcs7(key)
+                    .unwrap()
+                    .decrypt(&mut one_shot, context().into())
+                    .unwrap();
+                assert_eq!(plaintext.as_slice(), decrypted);
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_ctr_matches_one_shot() {
+        let key_bytes = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        for len in [0, 1, 15, 16, 17, 100] {
+            let plaintext: Vec<u8> = (0..len).map(|i| u8::try_from(i).unwrap()).collect();
+
+            let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+            let mut expected = plaintext.clone();
+            EncryptingKey::ctr(key)
+                .unwrap()
+                .less_safe_encrypt(&mut expected, context())
+                .unwrap();
+
+            for step in [1, 3, 15, 16, 17, 64] {
+                let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+                let key = StreamingEncryptingKey::less_safe_ctr(key, context()).unwrap();
+                let ciphertext = encrypt_streaming(key, &plaintext, step);
+                assert_eq!(expected, ciphertext);
+
+                let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+                let key = StreamingDecryptingKey::ctr(key, context().into()).unwrap();
+                assert_eq!(plaintext, decrypt_streaming(key, &ciphertext, step));
+            }
+
+            let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+            let mut one_shot = expected.clone();
+            let decrypted = DecryptingKey::ctr(key)
+                .unwrap()
+                .decrypt(&mut one_shot, context().into())
+                .unwrap();
+            assert_eq!(plaintext.as_slice(), decrypted);
+        }
+    }
+
+    #[test]
+    fn test_streaming_errors() {
+        let key_bytes = [0u8; 16];
+
+        // Output buffers that are too small.
+        let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+        let mut key = StreamingEncryptingKey::cbc_pkcs7(key).unwrap();
+        assert!(key.update(&[0u8; 16], &mut [0u8; 30]).is_err());
+        assert!(key.update(&[0u8; 16], &mut [0u8; 31]).is_ok());
+        assert!(key.finish(&mut [0u8; 15]).is_err());
+
+        let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+        let mut key = StreamingEncryptingKey::ctr(key).unwrap();
+        assert!(key.update(&[0u8; 16], &mut [0u8; 15]).is_err());
+
+        // Ciphertext that is not a multiple of the block length.
+        let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+        let mut key = StreamingDecryptingKey::cbc_pkcs7(key, context().into()).unwrap();
+        let mut output = [0u8; 32];
+        assert_eq!(
+            0,
+            key.update(&[0u8; 17], &mut output).unwrap().written().len()
+        );
+        assert!(key.finish(&mut output).is_err());
+
+        // Invalid padding. Encrypting zeros in CTR mode yields the encryption of the IV, which
+        // decrypts in CBC mode to an all-zero block.
+        let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+        let mut ciphertext = [0u8; 16];
+        EncryptingKey::ctr(key)
+            .unwrap()
+            .less_safe_encrypt(&mut ciphertext, context())
+            .unwrap();
+        let key = UnboundCipherKey::new(&AES_128, &key_bytes).unwrap();
+        let mut key = StreamingDecryptingKey::cbc_pkcs7(key, context().into()).unwrap();
+        key.update(&ciphertext, &mut output).unwrap();
+        assert!(key.finish(&mut output).is_err());
+    }
+
+    #[test]
+    fn test_streaming_debug() {
+        let key = UnboundCipherKey::new(&AES_128, &[0u8; 16]).unwrap();
+        let key = StreamingEncryptingKey::ctr(key).unwrap();
+        assert_eq!(
+            "StreamingEncryptingKey { key: UnboundCipherKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 } }, mode: CTR, .. }",
+            format!("{key:?}")
+        );
+    }
+}
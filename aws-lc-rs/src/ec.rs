@@ -4,35 +4,33 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use crate::error::{KeyRejected, Unspecified};
+#[cfg(feature = "pem")]
+use crate::pem;
 use core::fmt;
 
 use crate::ptr::{ConstPointer, DetachableLcPtr, LcPtr};
 
 use crate::signature::{Signature, VerificationAlgorithm};
-use crate::{digest, sealed, test};
+use crate::{cbs, digest, sealed, spki, test};
 use aws_lc::{
-    point_conversion_form_t, ECDSA_SIG_from_bytes, ECDSA_SIG_get0_r, ECDSA_SIG_get0_s,
+    point_conversion_form_t, CBS_len, ECDSA_SIG_from_bytes, ECDSA_SIG_get0_r, ECDSA_SIG_get0_s,
     ECDSA_SIG_new, ECDSA_SIG_set0, ECDSA_SIG_to_bytes, ECDSA_do_verify, EC_GROUP_get_curve_name,
     EC_GROUP_new_by_curve_name, EC_KEY_get0_group, EC_KEY_get0_public_key, EC_KEY_new,
-    EC_KEY_new_by_curve_name, EC_KEY_set_group, EC_KEY_set_private_key, EC_KEY_set_public_key,
-    EC_POINT_new, EC_POINT_oct2point, EC_POINT_point2oct, NID_X9_62_prime256v1, NID_secp256k1,
-    NID_secp384r1, NID_secp521r1, BIGNUM, ECDSA_SIG, EC_GROUP, EC_KEY, EC_POINT,
+    EC_KEY_new_by_curve_name, EC_KEY_parse_private_key, EC_KEY_set_group, EC_KEY_set_private_key,
+    EC_KEY_set_public_key, EC_POINT_mul, EC_POINT_new, EC_POINT_oct2point, EC_POINT_point2oct,
+    EVP_PKEY_assign_EC_KEY, EVP_PKEY_new, NID_X9_62_prime256v1, NID_secp256k1, NID_secp384r1,
+    NID_secp521r1, BIGNUM, ECDSA_SIG, EC_GROUP, EC_KEY, EC_POINT, EVP_PKEY,
 };
 #[cfg(feature = "fips")]
 use aws_lc::{EC_KEY_check_fips, EC_KEY_generate_key_fips};
 #[cfg(not(feature = "fips"))]
 use aws_lc::{EC_KEY_check_key, EC_KEY_generate_key};
 
-#[cfg(test)]
-use aws_lc::EC_POINT_mul;
-
 use std::fmt::{Debug, Formatter};
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::os::raw::{c_int, c_uint};
-#[cfg(test)]
-use std::ptr::null;
-use std::ptr::null_mut;
+use std::ptr::{null, null_mut};
 use std::slice;
 
 #[cfg(feature = "ring-sig-verify")]
@@ -110,25 +108,96 @@ impl AlgorithmID {
     }
 }
 
+/// An elliptic curve public key, in uncompressed point form.
 #[derive(Clone)]
-pub struct PublicKey(Box<[u8]>);
+pub struct PublicKey {
+    key: Box<[u8]>,
+    nid: i32,
+}
 
 impl Debug for PublicKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str(&format!("PublicKey(\"{}\")", test::to_hex(self.0.as_ref())))
+        f.write_str(&format!(
+            "PublicKey(\"{}\")",
+            test::to_hex(self.key.as_ref())
+        ))
     }
 }
 
 impl PublicKey {
-    fn new(pubkey_box: Box<[u8]>) -> Self {
-        PublicKey(pubkey_box)
+    fn new(key: Box<[u8]>, nid: i32) -> Self {
+        PublicKey { key, nid }
+    }
+
+    /// Parses a DER-encoded X.509 `SubjectPublicKeyInfo` containing an EC public key on
+    /// any of the supported curves. The curve is detected from the encoding.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `spki` is not a valid encoding, or if it does not contain
+    /// an EC public key on a supported curve.
+    pub fn from_der(spki: &[u8]) -> Result<Self, KeyRejected> {
+        let evp_pkey = LcPtr::<*mut EVP_PKEY>::parse_public_key(spki)?;
+        let ec_key = evp_pkey.get_ec_key()?;
+        let nid = unsafe {
+            let ec_group = ConstPointer::new(EC_KEY_get0_group(*ec_key))?;
+            EC_GROUP_get_curve_name(*ec_group)
+        };
+        if ![
+            NID_X9_62_prime256v1,
+            NID_secp384r1,
+            NID_secp521r1,
+            NID_secp256k1,
+        ]
+        .contains(&nid)
+        {
+            return Err(KeyRejected::wrong_algorithm());
+        }
+        marshal_public_key(&ec_key.as_const())
+            .map_err(|Unspecified| KeyRejected::unexpected_error())
+    }
+
+    /// Serializes this public key as a DER-encoded X.509 `SubjectPublicKeyInfo`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn as_der(&self) -> Result<spki::Document, Unspecified> {
+        let evp_pkey = unsafe { evp_pkey_from_public_point(self.nid, &self.key)? };
+        evp_pkey.marshall_public_key()
+    }
+
+    /// Parses a PEM-encoded X.509 `SubjectPublicKeyInfo` (`PUBLIC KEY`) as described for
+    /// `from_der`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the PEM label is for another kind of key, or as described
+    /// for `from_der`.
+    #[cfg(feature = "pem")]
+    pub fn from_pem(pem: &str) -> Result<Self, KeyRejected> {
+        let (label, der) = pem::decode(pem)?;
+        match label {
+            pem::Label::PublicKey => Self::from_der(&der),
+            pem::Label::RsaPublicKey => Err(KeyRejected::wrong_algorithm()),
+            pem::Label::PrivateKey | pem::Label::RsaPrivateKey | pem::Label::EcPrivateKey => {
+                Err(KeyRejected::unexpected_pem_label())
+            }
+        }
+    }
+
+    /// Serializes this public key as a PEM-encoded X.509 `SubjectPublicKeyInfo`
+    /// (`PUBLIC KEY`).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "pem")]
+    pub fn to_pem(&self) -> Result<pem::Document, Unspecified> {
+        pem::encode(pem::Label::PublicKey, self.as_der()?.as_ref())
     }
 }
 
 impl AsRef<[u8]> for PublicKey {
     #[inline]
     fn as_ref(&self) -> &[u8] {
-        self.0.as_ref()
+        self.key.as_ref()
     }
 }
 
@@ -157,6 +226,18 @@ impl VerificationAlgorithm for EcdsaVerificationAlgorithm {
         msg: &[u8],
         signature: &[u8],
     ) -> Result<(), Unspecified> {
+        self.verify_digest_sig(public_key, &digest::digest(self.digest, msg), signature)
+    }
+
+    fn verify_digest_sig(
+        &self,
+        public_key: &[u8],
+        digest: &digest::Digest,
+        signature: &[u8],
+    ) -> Result<(), Unspecified> {
+        if digest.algorithm() != self.digest {
+            return Err(Unspecified);
+        }
         unsafe {
             let ec_group = ec_group_from_nid(self.id.nid())?;
             let ec_point = ec_point_from_bytes(&ec_group, public_key)?;
@@ -166,8 +247,7 @@ impl VerificationAlgorithm for EcdsaVerificationAlgorithm {
                 EcdsaSignatureFormat::ASN1 => ecdsa_sig_from_asn1(signature),
                 EcdsaSignatureFormat::Fixed => ecdsa_sig_from_fixed(self.id, signature),
             }?;
-            let msg_digest = digest::digest(self.digest, msg);
-            let msg_digest = msg_digest.as_ref();
+            let msg_digest = digest.as_ref();
 
             if 1 != ECDSA_do_verify(msg_digest.as_ptr(), msg_digest.len(), *ecdsa_sig, *ec_key) {
                 return Err(Unspecified);
@@ -179,7 +259,7 @@ impl VerificationAlgorithm for EcdsaVerificationAlgorithm {
 }
 
 #[inline]
-unsafe fn validate_ec_key(
+pub(crate) unsafe fn validate_ec_key(
     ec_key: &ConstPointer<EC_KEY>,
     expected_curve_nid: i32,
 ) -> Result<(), KeyRejected> {
@@ -220,10 +300,45 @@ pub(crate) fn marshal_public_key(ec_key: &ConstPointer<EC_KEY>) -> Result<Public
         let mut pub_key_bytes = [0u8; PUBLIC_KEY_MAX_LEN];
         let key_len = marshal_public_key_to_buffer(&mut pub_key_bytes, ec_key)?;
         let pub_key = Vec::from(&pub_key_bytes[0..key_len]);
-        Ok(PublicKey::new(pub_key.into_boxed_slice()))
+        let ec_group = ConstPointer::new(EC_KEY_get0_group(**ec_key))?;
+        let nid = EC_GROUP_get_curve_name(*ec_group);
+        Ok(PublicKey::new(pub_key.into_boxed_slice(), nid))
     }
 }
 
+/// Parses a DER-encoded SEC 1 `ECPrivateKey` on the curve identified by `nid`,
+/// rejecting trailing data.
+pub(crate) unsafe fn ec_key_from_sec1_der(
+    nid: i32,
+    sec1: &[u8],
+) -> Result<LcPtr<*mut EC_KEY>, KeyRejected> {
+    let ec_group = ec_group_from_nid(nid)?;
+    let mut cbs = cbs::build_CBS(sec1);
+    let ec_key = LcPtr::new(EC_KEY_parse_private_key(&mut cbs, *ec_group))
+        .map_err(|()| KeyRejected::invalid_encoding())?;
+    if 0 != CBS_len(&cbs) {
+        return Err(KeyRejected::invalid_encoding());
+    }
+    validate_ec_key(&ec_key.as_const(), nid)?;
+    Ok(ec_key)
+}
+
+/// Builds an `EVP_PKEY` from an encoded public point on the curve identified by `nid`.
+pub(crate) unsafe fn evp_pkey_from_public_point(
+    nid: i32,
+    public_key: &[u8],
+) -> Result<LcPtr<*mut EVP_PKEY>, Unspecified> {
+    let ec_group = ec_group_from_nid(nid)?;
+    let ec_point = ec_point_from_bytes(&ec_group, public_key)?;
+    let ec_key = ec_key_from_public_point(&ec_group, &ec_point)?;
+    let evp_pkey = LcPtr::new(EVP_PKEY_new())?;
+    if 1 != EVP_PKEY_assign_EC_KEY(*evp_pkey, *ec_key) {
+        return Err(Unspecified);
+    }
+    ec_key.detach();
+    Ok(evp_pkey)
+}
+
 #[inline]
 pub(crate) unsafe fn ec_key_from_public_point(
     ec_group: &LcPtr<*mut EC_GROUP>,
@@ -239,7 +354,6 @@ pub(crate) unsafe fn ec_key_from_public_point(
     Ok(ec_key)
 }
 
-#[cfg(test)]
 pub(crate) unsafe fn ec_key_from_private(
     ec_group: &ConstPointer<EC_GROUP>,
     private_big_num: &ConstPointer<BIGNUM>,
@@ -5,8 +5,10 @@
 
 use crate::ec::{validate_ec_key, EcdsaSignatureFormat, EcdsaSigningAlgorithm, PublicKey};
 use crate::error::{KeyRejected, Unspecified};
+#[cfg(feature = "pem")]
+use crate::pem;
 use crate::pkcs8::{Document, Version};
-use crate::ptr::{DetachableLcPtr, LcPtr};
+use crate::ptr::{ConstPointer, DetachableLcPtr, LcPtr};
 use crate::rand::SecureRandom;
 use crate::signature::{KeyPair, Signature};
 use crate::{digest, ec};
@@ -15,11 +17,13 @@ use aws_lc::EC_KEY_generate_key;
 #[cfg(feature = "fips")]
 use aws_lc::EC_KEY_generate_key_fips;
 use aws_lc::{
-    ECDSA_do_sign, EC_KEY_new_by_curve_name, EVP_PKEY_assign_EC_KEY, EVP_PKEY_new, EC_KEY, EVP_PKEY,
+    BN_bn2bin_padded, ECDSA_do_sign, EC_KEY_get0_private_key, EC_KEY_new_by_curve_name,
+    EVP_PKEY_assign_EC_KEY, EVP_PKEY_new, EVP_PKEY_set1_EC_KEY, ECDSA_SIG, EC_KEY, EVP_PKEY,
 };
 use std::fmt;
 
 use std::fmt::{Debug, Formatter};
+use zeroize::Zeroize;
 
 /// An ECDSA key pair, used for signing.
 #[allow(clippy::module_name_repetitions)]
@@ -107,6 +111,38 @@ impl EcdsaKeyPair {
         }
     }
 
+    /// Parses a PEM-encoded ECDSA private key for the curve of `alg`: either an
+    /// unencrypted PKCS#8 document (`PRIVATE KEY`) or a SEC 1 `ECPrivateKey` structure
+    /// (`EC PRIVATE KEY`).
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the PEM label is for another kind of key, or if the key
+    /// is not a valid key for `alg`.
+    #[cfg(feature = "pem")]
+    pub fn from_pem(alg: &'static EcdsaSigningAlgorithm, pem: &str) -> Result<Self, KeyRejected> {
+        let (label, der) = pem::decode(pem)?;
+        match label {
+            pem::Label::PrivateKey => Self::from_pkcs8(alg, &der),
+            pem::Label::EcPrivateKey => unsafe {
+                let ec_key = ec::ec_key_from_sec1_der(alg.id.nid(), &der)?;
+                Ok(Self::new(alg, ec_key)?)
+            },
+            pem::Label::RsaPrivateKey => Err(KeyRejected::wrong_algorithm()),
+            pem::Label::PublicKey | pem::Label::RsaPublicKey => {
+                Err(KeyRejected::unexpected_pem_label())
+            }
+        }
+    }
+
+    /// Serializes this key pair as a PEM-encoded PKCS#8 v1 document (`PRIVATE KEY`).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "pem")]
+    pub fn to_pem(&self) -> Result<pem::Document, Unspecified> {
+        pem::encode(pem::Label::PrivateKey, self.to_pkcs8v1()?.as_ref())
+    }
+
     /// Generates a new key pair and returns the key pair serialized as a
     /// PKCS#8 v1 document.
     ///
@@ -163,6 +199,44 @@ impl EcdsaKeyPair {
         }
     }
 
+    /// Serializes this key pair as a PKCS#8 v1 `PrivateKeyInfo` document containing an
+    /// `ECPrivateKey` with the public key.
+    ///
+    /// There is no `to_pkcs8v2` for ECDSA keys: AWS-LC's `EVP_marshal_private_key_v2` can
+    /// only encode Ed25519 keys. The v1 document already includes the public key, inside
+    /// the `ECPrivateKey`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn to_pkcs8v1(&self) -> Result<Document, Unspecified> {
+        unsafe {
+            let evp_pkey = LcPtr::new(EVP_PKEY_new())?;
+            if 1 != EVP_PKEY_set1_EC_KEY(*evp_pkey, *self.ec_key) {
+                return Err(Unspecified);
+            }
+            evp_pkey.marshall_private_key(Version::V1)
+        }
+    }
+
+    /// Returns the private key encoded as a big-endian fixed-length integer, the
+    /// format accepted by `EcdsaKeyPair::from_private_key_and_public_key`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn private_key_bytes(&self) -> Result<EcdsaPrivateKeyBytes, Unspecified> {
+        let mut bytes = EcdsaPrivateKeyBytes {
+            bytes: [0u8; ec::SCALAR_MAX_BYTES],
+            len: (self.algorithm.bits as usize + 7) / 8,
+        };
+        unsafe {
+            let priv_key = ConstPointer::new(EC_KEY_get0_private_key(*self.ec_key))?;
+            if 1 != BN_bn2bin_padded(bytes.bytes.as_mut_ptr(), bytes.len, *priv_key) {
+                return Err(Unspecified);
+            }
+        }
+        Ok(bytes)
+    }
+
     /// Returns the signature of the message using a random nonce.
     ///
     /// # *ring* Compatibility
@@ -172,17 +246,68 @@ impl EcdsaKeyPair {
     /// `error::Unspecified` on internal error.
     ///
     #[inline]
-    pub fn sign(&self, _rng: &dyn SecureRandom, message: &[u8]) -> Result<Signature, Unspecified> {
+    pub fn sign(&self, rng: &dyn SecureRandom, message: &[u8]) -> Result<Signature, Unspecified> {
+        self.sign_digest(rng, &digest::digest(self.algorithm.digest, message))
+    }
+
+    /// Returns the signature of the message digest `digest` using a random nonce.
+    ///
+    /// This is useful when the message is too large to hold in memory and is
+    /// digested incrementally with a `digest::Context`.
+    ///
+    /// # *ring* Compatibility
+    /// Our implementation ignores the `SecureRandom` parameter.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error, or if the algorithm of `digest` is not
+    /// the digest algorithm of the signing algorithm.
+    pub fn sign_digest(
+        &self,
+        _rng: &dyn SecureRandom,
+        digest: &digest::Digest,
+    ) -> Result<Signature, Unspecified> {
+        if digest.algorithm() != self.algorithm.digest {
+            return Err(Unspecified);
+        }
         unsafe {
-            let digest = digest::digest(self.algorithm.digest, message);
             let digest = digest.as_ref();
             let ecdsa_sig = LcPtr::new(ECDSA_do_sign(digest.as_ptr(), digest.len(), *self.ec_key))?;
-            match self.algorithm.sig_format {
-                EcdsaSignatureFormat::ASN1 => ec::ecdsa_sig_to_asn1(&ecdsa_sig),
-                EcdsaSignatureFormat::Fixed => {
-                    ec::ecdsa_sig_to_fixed(self.algorithm.id, &ecdsa_sig)
-                }
-            }
+            self.encode_signature(&ecdsa_sig)
+        }
+    }
+
+    unsafe fn encode_signature(
+        &self,
+        ecdsa_sig: &LcPtr<*mut ECDSA_SIG>,
+    ) -> Result<Signature, Unspecified> {
+        match self.algorithm.sig_format {
+            EcdsaSignatureFormat::ASN1 => ec::ecdsa_sig_to_asn1(ecdsa_sig),
+            EcdsaSignatureFormat::Fixed => ec::ecdsa_sig_to_fixed(self.algorithm.id, ecdsa_sig),
         }
     }
 }
+
+/// The raw encoding of an ECDSA private key. It is zeroized when dropped.
+pub struct EcdsaPrivateKeyBytes {
+    bytes: [u8; ec::SCALAR_MAX_BYTES],
+    len: usize,
+}
+
+impl AsRef<[u8]> for EcdsaPrivateKeyBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl Drop for EcdsaPrivateKeyBytes {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl Debug for EcdsaPrivateKeyBytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("EcdsaPrivateKeyBytes()")
+    }
+}
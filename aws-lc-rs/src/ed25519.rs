@@ -4,14 +4,17 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use crate::error::{KeyRejected, Unspecified};
+#[cfg(feature = "pem")]
+use crate::pem;
 use crate::pkcs8::{Document, Version};
 use crate::ptr::LcPtr;
 use crate::rand::SecureRandom;
 use crate::signature::{KeyPair, Signature, VerificationAlgorithm};
-use crate::{constant_time, sealed, test};
+use crate::{constant_time, digest, sealed, spki, test};
 use aws_lc::{
     ED25519_keypair_from_seed, ED25519_sign, ED25519_verify, EVP_PKEY_get_raw_private_key,
-    EVP_PKEY_get_raw_public_key, EVP_PKEY_new_raw_private_key, EVP_PKEY, EVP_PKEY_ED25519,
+    EVP_PKEY_get_raw_public_key, EVP_PKEY_new_raw_private_key, EVP_PKEY_new_raw_public_key,
+    EVP_PKEY, EVP_PKEY_ED25519,
 };
 use std::fmt;
 use std::fmt::{Debug, Formatter};
@@ -70,6 +73,16 @@ impl VerificationAlgorithm for EdDSAParameters {
             Ok(())
         }
     }
+
+    /// Ed25519 signs the message itself, so pre-hashed messages are not supported.
+    fn verify_digest_sig(
+        &self,
+        _public_key: &[u8],
+        _digest: &digest::Digest,
+        _signature: &[u8],
+    ) -> Result<(), Unspecified> {
+        Err(Unspecified)
+    }
 }
 
 /// An Ed25519 key pair, for signing.
@@ -88,12 +101,80 @@ impl Debug for Ed25519KeyPair {
     }
 }
 
+/// An Ed25519 public key.
 #[derive(Clone)]
 #[allow(clippy::module_name_repetitions)]
 pub struct PublicKey {
     public_key: [u8; ED25519_PUBLIC_KEY_LEN],
 }
 
+impl PublicKey {
+    /// Parses a DER-encoded X.509 `SubjectPublicKeyInfo` containing an Ed25519 public
+    /// key (RFC 8410).
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `spki` is not a valid encoding, or if it does not contain
+    /// an Ed25519 public key.
+    pub fn from_der(spki: &[u8]) -> Result<Self, KeyRejected> {
+        let evp_pkey = LcPtr::<*mut EVP_PKEY>::parse_public_key(spki)?;
+        evp_pkey.validate_as_ed25519()?;
+
+        let mut public_key = [0u8; ED25519_PUBLIC_KEY_LEN];
+        let mut out_len: usize = ED25519_PUBLIC_KEY_LEN;
+        if 1 != unsafe {
+            EVP_PKEY_get_raw_public_key(*evp_pkey, public_key.as_mut_ptr(), &mut out_len)
+        } {
+            return Err(KeyRejected::wrong_algorithm());
+        }
+        Ok(PublicKey { public_key })
+    }
+
+    /// Serializes this public key as a DER-encoded X.509 `SubjectPublicKeyInfo`
+    /// (RFC 8410).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn as_der(&self) -> Result<spki::Document, Unspecified> {
+        let evp_pkey = LcPtr::new(unsafe {
+            EVP_PKEY_new_raw_public_key(
+                EVP_PKEY_ED25519,
+                null_mut(),
+                self.public_key.as_ptr(),
+                ED25519_PUBLIC_KEY_LEN,
+            )
+        })?;
+        evp_pkey.marshall_public_key()
+    }
+
+    /// Parses a PEM-encoded X.509 `SubjectPublicKeyInfo` (`PUBLIC KEY`) containing an
+    /// Ed25519 public key.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the PEM label is for another kind of key, or as described
+    /// for `from_der`.
+    #[cfg(feature = "pem")]
+    pub fn from_pem(pem: &str) -> Result<Self, KeyRejected> {
+        let (label, der) = pem::decode(pem)?;
+        match label {
+            pem::Label::PublicKey => Self::from_der(&der),
+            pem::Label::RsaPublicKey => Err(KeyRejected::wrong_algorithm()),
+            pem::Label::PrivateKey | pem::Label::RsaPrivateKey | pem::Label::EcPrivateKey => {
+                Err(KeyRejected::unexpected_pem_label())
+            }
+        }
+    }
+
+    /// Serializes this public key as a PEM-encoded X.509 `SubjectPublicKeyInfo`
+    /// (`PUBLIC KEY`).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "pem")]
+    pub fn to_pem(&self) -> Result<pem::Document, Unspecified> {
+        pem::encode(pem::Label::PublicKey, self.as_der()?.as_ref())
+    }
+}
+
 impl AsRef<[u8]> for PublicKey {
     #[inline]
     fn as_ref(&self) -> &[u8] {
@@ -283,6 +364,74 @@ impl Ed25519KeyPair {
         }
     }
 
+    /// Serializes this key pair as a PKCS#8 v1 `PrivateKeyInfo` document (RFC 5208).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn to_pkcs8v1(&self) -> Result<Document, Unspecified> {
+        self.to_evp_pkey()?.marshall_private_key(Version::V1)
+    }
+
+    /// Serializes this key pair as a PKCS#8 v2 `OneAsymmetricKey` document with the
+    /// public key, as described in [RFC 5958 Section 2].
+    ///
+    /// [RFC 5958 Section 2]: https://tools.ietf.org/html/rfc5958#section-2
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn to_pkcs8v2(&self) -> Result<Document, Unspecified> {
+        self.to_evp_pkey()?.marshall_private_key(Version::V2)
+    }
+
+    /// Parses a PEM-encoded unencrypted PKCS#8 v1 or v2 Ed25519 private key
+    /// (`PRIVATE KEY`).
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the PEM label is for another kind of key, or as described
+    /// for `from_pkcs8`.
+    #[cfg(feature = "pem")]
+    pub fn from_pem(pem: &str) -> Result<Self, KeyRejected> {
+        let (label, der) = pem::decode(pem)?;
+        match label {
+            pem::Label::PrivateKey => Self::from_pkcs8(&der),
+            pem::Label::RsaPrivateKey | pem::Label::EcPrivateKey => {
+                Err(KeyRejected::wrong_algorithm())
+            }
+            pem::Label::PublicKey | pem::Label::RsaPublicKey => {
+                Err(KeyRejected::unexpected_pem_label())
+            }
+        }
+    }
+
+    /// Serializes this key pair as a PEM-encoded PKCS#8 v2 document (`PRIVATE KEY`).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "pem")]
+    pub fn to_pem(&self) -> Result<pem::Document, Unspecified> {
+        pem::encode(pem::Label::PrivateKey, self.to_pkcs8v2()?.as_ref())
+    }
+
+    /// Returns the private key seed, the format accepted by
+    /// `Ed25519KeyPair::from_seed_and_public_key`.
+    #[must_use]
+    pub fn seed(&self) -> Ed25519Seed {
+        let mut seed = [0u8; ED25519_SEED_LEN];
+        seed.copy_from_slice(&self.private_key[..ED25519_SEED_LEN]);
+        Ed25519Seed(seed)
+    }
+
+    fn to_evp_pkey(&self) -> Result<LcPtr<*mut EVP_PKEY>, Unspecified> {
+        unsafe {
+            Ok(LcPtr::new(EVP_PKEY_new_raw_private_key(
+                EVP_PKEY_ED25519,
+                null_mut(),
+                self.private_key.as_ptr(),
+                ED25519_PRIVATE_KEY_SEED_LEN,
+            ))?)
+        }
+    }
+
     /// Returns the signature of the message msg.
     ///
     /// # Panics
@@ -315,6 +464,28 @@ impl Ed25519KeyPair {
     }
 }
 
+/// The private key seed of an Ed25519 key pair. It is zeroized when dropped.
+pub struct Ed25519Seed([u8; ED25519_SEED_LEN]);
+
+impl AsRef<[u8]> for Ed25519Seed {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Drop for Ed25519Seed {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Debug for Ed25519Seed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("Ed25519Seed()")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ed25519::Ed25519KeyPair;
@@ -0,0 +1,424 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Public-key encryption using RSA.
+//!
+//! RSA-OAEP ([RFC 8017 Section 7.1]) is supported with SHA-1, SHA-256, SHA-384 and
+//! SHA-512, using the same digest for MGF1. Decryption of RSA PKCS#1 v1.5 ciphertexts
+//! ([RFC 8017 Section 7.2]) is available for interoperability with legacy systems only;
+//! new protocols should use OAEP.
+//!
+//! [RFC 8017 Section 7.1]: https://www.rfc-editor.org/rfc/rfc8017#section-7.1
+//! [RFC 8017 Section 7.2]: https://www.rfc-editor.org/rfc/rfc8017#section-7.2
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::encryption::{PrivateDecryptingKey, RSA_OAEP_SHA256};
+//! use aws_lc_rs::signature::RsaKeyPair;
+//!
+//! let pkcs8 = RsaKeyPair::generate_pkcs8(2048)?;
+//! let private_key = PrivateDecryptingKey::from_pkcs8(pkcs8.as_ref()).unwrap();
+//! let public_key = private_key.public_key()?;
+//!
+//! let mut ciphertext = vec![0u8; public_key.key_size_bytes()];
+//! let ciphertext =
+//!     public_key.encrypt(&RSA_OAEP_SHA256, b"data key", &mut ciphertext, Some(b"label"))?;
+//!
+//! let mut plaintext = vec![0u8; private_key.key_size_bytes()];
+//! let plaintext =
+//!     private_key.decrypt(&RSA_OAEP_SHA256, ciphertext, &mut plaintext, Some(b"label"))?;
+//! assert_eq!(b"data key", plaintext);
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+
+use crate::derive_debug_via_id;
+use crate::digest::{self, match_digest_type};
+use crate::error::{KeyRejected, Unspecified};
+use crate::ptr::LcPtr;
+use crate::rsa::{build_private_RSA, build_public_RSA};
+#[cfg(feature = "fips")]
+use aws_lc::RSA_check_fips;
+#[cfg(not(feature = "fips"))]
+use aws_lc::RSA_check_key;
+use aws_lc::{
+    EVP_PKEY_CTX_new, EVP_PKEY_CTX_set0_rsa_oaep_label, EVP_PKEY_CTX_set_rsa_mgf1_md,
+    EVP_PKEY_CTX_set_rsa_oaep_md, EVP_PKEY_CTX_set_rsa_padding, EVP_PKEY_decrypt,
+    EVP_PKEY_decrypt_init, EVP_PKEY_encrypt, EVP_PKEY_encrypt_init, EVP_PKEY_new,
+    EVP_PKEY_set1_RSA, EVP_PKEY_size, OPENSSL_memdup, RSA_bits, RSA_public_key_to_bytes, EVP_PKEY,
+    EVP_PKEY_CTX, RSA, RSA_PKCS1_OAEP_PADDING, RSA_PKCS1_PADDING,
+};
+use core::fmt;
+use std::fmt::{Debug, Formatter};
+use std::mem::MaybeUninit;
+use std::ptr::null_mut;
+
+const MIN_RSA_BITS: u32 = 2048;
+const MAX_RSA_BITS: u32 = 8192;
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum OaepAlgorithmId {
+    RSA_OAEP_SHA1,
+    RSA_OAEP_SHA256,
+    RSA_OAEP_SHA384,
+    RSA_OAEP_SHA512,
+}
+
+/// An RSA-OAEP encryption algorithm.
+pub struct OaepAlgorithm {
+    id: OaepAlgorithmId,
+    digest: &'static digest::Algorithm,
+}
+
+derive_debug_via_id!(OaepAlgorithm);
+
+impl PartialEq for OaepAlgorithm {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for OaepAlgorithm {}
+
+/// RSA-OAEP with SHA-1 as the digest and for MGF1.
+///
+/// SHA-1 is still considered secure for use with OAEP, but SHA-256 or larger is
+/// recommended for new applications.
+pub static RSA_OAEP_SHA1: OaepAlgorithm = OaepAlgorithm {
+    id: OaepAlgorithmId::RSA_OAEP_SHA1,
+    digest: &digest::SHA1_FOR_LEGACY_USE_ONLY,
+};
+
+/// RSA-OAEP with SHA-256 as the digest and for MGF1.
+pub static RSA_OAEP_SHA256: OaepAlgorithm = OaepAlgorithm {
+    id: OaepAlgorithmId::RSA_OAEP_SHA256,
+    digest: &digest::SHA256,
+};
+
+/// RSA-OAEP with SHA-384 as the digest and for MGF1.
+pub static RSA_OAEP_SHA384: OaepAlgorithm = OaepAlgorithm {
+    id: OaepAlgorithmId::RSA_OAEP_SHA384,
+    digest: &digest::SHA384,
+};
+
+/// RSA-OAEP with SHA-512 as the digest and for MGF1.
+pub static RSA_OAEP_SHA512: OaepAlgorithm = OaepAlgorithm {
+    id: OaepAlgorithmId::RSA_OAEP_SHA512,
+    digest: &digest::SHA512,
+};
+
+/// An RSA public key used for encryption.
+pub struct PublicEncryptingKey {
+    evp_pkey: LcPtr<*mut EVP_PKEY>,
+}
+
+// An `EVP_PKEY` is not modified after it is constructed.
+unsafe impl Send for PublicEncryptingKey {}
+unsafe impl Sync for PublicEncryptingKey {}
+
+impl PublicEncryptingKey {
+    /// Parses a DER-encoded `RSAPublicKey` structure (RFC 8017).
+    ///
+    /// The public modulus must be at least 2048 bits and no larger than 8192 bits.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `public_key` is not a valid RSA public key, or if its
+    /// size is not supported.
+    pub fn from_der(public_key: &[u8]) -> Result<Self, KeyRejected> {
+        let rsa = unsafe { build_public_RSA(public_key) }
+            .map_err(|Unspecified| KeyRejected::invalid_encoding())?;
+        Ok(Self {
+            evp_pkey: rsa_to_evp_pkey(&rsa)?,
+        })
+    }
+
+    /// Encrypts `plaintext` with RSA-OAEP, writing the result to the start of
+    /// `ciphertext` and returning it.
+    ///
+    /// `label` is the optional OAEP label; `None` is equivalent to an empty label.
+    /// `ciphertext` must be at least `key_size_bytes()` long, and `plaintext` must be
+    /// no longer than `max_plaintext_size(algorithm)`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `plaintext` is too long, `ciphertext` is too short, or
+    /// on internal error.
+    pub fn encrypt<'a>(
+        &self,
+        algorithm: &'static OaepAlgorithm,
+        plaintext: &[u8],
+        ciphertext: &'a mut [u8],
+        label: Option<&[u8]>,
+    ) -> Result<&'a mut [u8], Unspecified> {
+        if ciphertext.len() < self.key_size_bytes() {
+            return Err(Unspecified);
+        }
+        let ctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new(*self.evp_pkey, null_mut()) })?;
+        if 1 != unsafe { EVP_PKEY_encrypt_init(*ctx) } {
+            return Err(Unspecified);
+        }
+        configure_oaep(&ctx, algorithm, label)?;
+
+        let mut out_len = ciphertext.len();
+        if 1 != unsafe {
+            EVP_PKEY_encrypt(
+                *ctx,
+                ciphertext.as_mut_ptr(),
+                &mut out_len,
+                plaintext.as_ptr(),
+                plaintext.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(&mut ciphertext[..out_len])
+    }
+
+    /// The length in bytes of the public modulus, which is also the length of a
+    /// ciphertext.
+    #[must_use]
+    pub fn key_size_bytes(&self) -> usize {
+        evp_pkey_size(&self.evp_pkey)
+    }
+
+    /// The maximum length in bytes of a plaintext that can be encrypted with
+    /// `algorithm`.
+    #[must_use]
+    pub fn max_plaintext_size(&self, algorithm: &'static OaepAlgorithm) -> usize {
+        let hash_len = algorithm.digest.output_len;
+        self.key_size_bytes().saturating_sub(2 * hash_len + 2)
+    }
+}
+
+impl Debug for PublicEncryptingKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "PublicEncryptingKey {{ bits: {} }}",
+            8 * self.key_size_bytes()
+        ))
+    }
+}
+
+/// An RSA private key used for decryption.
+pub struct PrivateDecryptingKey {
+    evp_pkey: LcPtr<*mut EVP_PKEY>,
+}
+
+// An `EVP_PKEY` is not modified after it is constructed.
+unsafe impl Send for PrivateDecryptingKey {}
+unsafe impl Sync for PrivateDecryptingKey {}
+
+impl PrivateDecryptingKey {
+    /// Parses an unencrypted PKCS#8-encoded RSA private key.
+    ///
+    /// The public modulus must be at least 2048 bits and no larger than 8192 bits.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `pkcs8` does not encode an RSA private key, or if the
+    /// key is otherwise not acceptable.
+    pub fn from_pkcs8(pkcs8: &[u8]) -> Result<Self, KeyRejected> {
+        let evp_pkey = LcPtr::try_from(pkcs8)?;
+        let rsa = evp_pkey.get_rsa()?;
+        validate_rsa_size(&rsa)?;
+        check_rsa_private_key(&rsa)?;
+        Ok(Self { evp_pkey })
+    }
+
+    /// Parses a DER-encoded `RSAPrivateKey` structure (RFC 8017).
+    ///
+    /// The public modulus must be at least 2048 bits and no larger than 8192 bits.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `input` is not a valid RSA private key, or if the key is
+    /// otherwise not acceptable.
+    pub fn from_der(input: &[u8]) -> Result<Self, KeyRejected> {
+        let rsa = unsafe { build_private_RSA(input)? };
+        check_rsa_private_key(&rsa)?;
+        Ok(Self {
+            evp_pkey: rsa_to_evp_pkey(&rsa)?,
+        })
+    }
+
+    /// Computes the public key corresponding to this private key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn public_key(&self) -> Result<PublicEncryptingKey, Unspecified> {
+        let rsa = self.evp_pkey.get_rsa()?;
+        let public_key = unsafe {
+            let mut out_bytes = MaybeUninit::<*mut u8>::uninit();
+            let mut out_len = MaybeUninit::<usize>::uninit();
+            if 1 != RSA_public_key_to_bytes(out_bytes.as_mut_ptr(), out_len.as_mut_ptr(), *rsa) {
+                return Err(Unspecified);
+            }
+            let out_bytes = LcPtr::new(out_bytes.assume_init())?;
+            std::slice::from_raw_parts(*out_bytes, out_len.assume_init()).to_vec()
+        };
+        Ok(PublicEncryptingKey::from_der(&public_key)?)
+    }
+
+    /// Decrypts an RSA-OAEP `ciphertext`, writing the result to the start of
+    /// `plaintext` and returning it.
+    ///
+    /// `label` must match the label used for encryption; `None` is equivalent to an
+    /// empty label. `plaintext` must be at least `key_size_bytes()` long.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if decryption fails, `plaintext` is too short, or on
+    /// internal error.
+    pub fn decrypt<'a>(
+        &self,
+        algorithm: &'static OaepAlgorithm,
+        ciphertext: &[u8],
+        plaintext: &'a mut [u8],
+        label: Option<&[u8]>,
+    ) -> Result<&'a mut [u8], Unspecified> {
+        let ctx = self.decrypt_ctx(plaintext)?;
+        configure_oaep(&ctx, algorithm, label)?;
+        decrypt(&ctx, ciphertext, plaintext)
+    }
+
+    /// Decrypts an RSA PKCS#1 v1.5 `ciphertext`, writing the result to the start of
+    /// `plaintext` and returning it. `plaintext` must be at least `key_size_bytes()`
+    /// long.
+    ///
+    /// PKCS#1 v1.5 encryption is vulnerable to padding-oracle attacks when decryption
+    /// failures are observable by an attacker; it is supported only for
+    /// interoperability with legacy systems.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if decryption fails, `plaintext` is too short, or on
+    /// internal error.
+    pub fn decrypt_pkcs1_for_legacy_use_only<'a>(
+        &self,
+        ciphertext: &[u8],
+        plaintext: &'a mut [u8],
+    ) -> Result<&'a mut [u8], Unspecified> {
+        let ctx = self.decrypt_ctx(plaintext)?;
+        if 1 != unsafe { EVP_PKEY_CTX_set_rsa_padding(*ctx, RSA_PKCS1_PADDING) } {
+            return Err(Unspecified);
+        }
+        decrypt(&ctx, ciphertext, plaintext)
+    }
+
+    /// The length in bytes of the public modulus, which is also the length of a
+    /// ciphertext.
+    #[must_use]
+    pub fn key_size_bytes(&self) -> usize {
+        evp_pkey_size(&self.evp_pkey)
+    }
+
+    fn decrypt_ctx(&self, plaintext: &[u8]) -> Result<LcPtr<*mut EVP_PKEY_CTX>, Unspecified> {
+        if plaintext.len() < self.key_size_bytes() {
+            return Err(Unspecified);
+        }
+        let ctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new(*self.evp_pkey, null_mut()) })?;
+        if 1 != unsafe { EVP_PKEY_decrypt_init(*ctx) } {
+            return Err(Unspecified);
+        }
+        Ok(ctx)
+    }
+}
+
+impl Debug for PrivateDecryptingKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "PrivateDecryptingKey {{ bits: {} }}",
+            8 * self.key_size_bytes()
+        ))
+    }
+}
+
+fn validate_rsa_size(rsa: &LcPtr<*mut RSA>) -> Result<(), KeyRejected> {
+    let bits = unsafe { RSA_bits(**rsa) };
+    if bits < MIN_RSA_BITS {
+        return Err(KeyRejected::too_small());
+    }
+    if bits > MAX_RSA_BITS {
+        return Err(KeyRejected::too_large());
+    }
+    Ok(())
+}
+
+fn check_rsa_private_key(rsa: &LcPtr<*mut RSA>) -> Result<(), KeyRejected> {
+    #[cfg(not(feature = "fips"))]
+    if 1 != unsafe { RSA_check_key(**rsa) } {
+        return Err(KeyRejected::inconsistent_components());
+    }
+
+    #[cfg(feature = "fips")]
+    if 1 != unsafe { RSA_check_fips(**rsa) } {
+        return Err(KeyRejected::inconsistent_components());
+    }
+
+    Ok(())
+}
+
+fn rsa_to_evp_pkey(rsa: &LcPtr<*mut RSA>) -> Result<LcPtr<*mut EVP_PKEY>, KeyRejected> {
+    validate_rsa_size(rsa)?;
+    unsafe {
+        let evp_pkey = LcPtr::new(EVP_PKEY_new())?;
+        if 1 != EVP_PKEY_set1_RSA(*evp_pkey, **rsa) {
+            return Err(KeyRejected::unexpected_error());
+        }
+        Ok(evp_pkey)
+    }
+}
+
+fn evp_pkey_size(evp_pkey: &LcPtr<*mut EVP_PKEY>) -> usize {
+    #[allow(clippy::cast_sign_loss)]
+    let size = unsafe { EVP_PKEY_size(**evp_pkey) } as usize;
+    size
+}
+
+fn configure_oaep(
+    ctx: &LcPtr<*mut EVP_PKEY_CTX>,
+    algorithm: &'static OaepAlgorithm,
+    label: Option<&[u8]>,
+) -> Result<(), Unspecified> {
+    let md = match_digest_type(&algorithm.digest.id);
+    if 1 != unsafe { EVP_PKEY_CTX_set_rsa_padding(**ctx, RSA_PKCS1_OAEP_PADDING) }
+        || 1 != unsafe { EVP_PKEY_CTX_set_rsa_oaep_md(**ctx, *md) }
+        || 1 != unsafe { EVP_PKEY_CTX_set_rsa_mgf1_md(**ctx, *md) }
+    {
+        return Err(Unspecified);
+    }
+    if let Some(label) = label {
+        if !label.is_empty() {
+            // `EVP_PKEY_CTX_set0_rsa_oaep_label` takes ownership of the label.
+            let label_ptr = unsafe { OPENSSL_memdup(label.as_ptr().cast(), label.len()) };
+            if label_ptr.is_null() {
+                return Err(Unspecified);
+            }
+            if 1 != unsafe {
+                EVP_PKEY_CTX_set0_rsa_oaep_label(**ctx, label_ptr.cast(), label.len())
+            } {
+                unsafe { aws_lc::OPENSSL_free(label_ptr) };
+                return Err(Unspecified);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn decrypt<'a>(
+    ctx: &LcPtr<*mut EVP_PKEY_CTX>,
+    ciphertext: &[u8],
+    plaintext: &'a mut [u8],
+) -> Result<&'a mut [u8], Unspecified> {
+    let mut out_len = plaintext.len();
+    if 1 != unsafe {
+        EVP_PKEY_decrypt(
+            **ctx,
+            plaintext.as_mut_ptr(),
+            &mut out_len,
+            ciphertext.as_ptr(),
+            ciphertext.len(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(&mut plaintext[..out_len])
+}
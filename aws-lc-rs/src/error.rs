@@ -162,6 +162,11 @@ impl KeyRejected {
         KeyRejected("PrivateModulusLenNotMultipleOf512Bits")
     }
 
+    #[cfg(feature = "pem")]
+    pub(crate) fn unexpected_pem_label() -> Self {
+        KeyRejected("UnexpectedPemLabel")
+    }
+
     pub(crate) fn unexpected_error() -> Self {
         KeyRejected("UnexpectedError")
     }
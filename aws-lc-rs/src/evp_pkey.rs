@@ -5,13 +5,12 @@ use crate::ec::PKCS8_DOCUMENT_MAX_LEN;
 use crate::error::{KeyRejected, Unspecified};
 use crate::pkcs8::{Document, Version};
 use crate::ptr::LcPtr;
-use crate::{cbb, cbs};
+use crate::{cbb, cbs, spki};
 use aws_lc::{
-    CBB_finish, EVP_PKEY_bits, EVP_PKEY_get1_EC_KEY, EVP_PKEY_get1_RSA, EVP_PKEY_id,
-    EVP_marshal_private_key, EVP_marshal_private_key_v2, EVP_parse_private_key, EC_KEY, EVP_PKEY,
-    RSA,
+    CBS_len, EVP_PKEY_bits, EVP_PKEY_get1_EC_KEY, EVP_PKEY_get1_RSA, EVP_PKEY_id,
+    EVP_marshal_private_key, EVP_marshal_private_key_v2, EVP_marshal_public_key,
+    EVP_parse_private_key, EVP_parse_public_key, EC_KEY, EVP_PKEY, RSA,
 };
-use std::mem::MaybeUninit;
 use std::os::raw::c_int;
 
 impl TryFrom<&[u8]> for LcPtr<*mut EVP_PKEY> {
@@ -27,6 +26,19 @@ impl TryFrom<&[u8]> for LcPtr<*mut EVP_PKEY> {
 }
 
 impl LcPtr<*mut EVP_PKEY> {
+    /// Parses a DER-encoded `SubjectPublicKeyInfo`, rejecting trailing data.
+    pub(crate) fn parse_public_key(spki: &[u8]) -> Result<Self, KeyRejected> {
+        unsafe {
+            let mut cbs = cbs::build_CBS(spki);
+            let evp_pkey = LcPtr::new(EVP_parse_public_key(&mut cbs))
+                .map_err(|()| KeyRejected::invalid_encoding())?;
+            if 0 != CBS_len(&cbs) {
+                return Err(KeyRejected::invalid_encoding());
+            }
+            Ok(evp_pkey)
+        }
+    }
+
     pub(crate) fn validate_as_ed25519(&self) -> Result<(), KeyRejected> {
         const ED25519_KEY_TYPE: c_int = aws_lc::EVP_PKEY_ED25519;
         const ED25519_MIN_BITS: c_int = 253;
@@ -96,25 +108,20 @@ impl LcPtr<*mut EVP_PKEY> {
                 }
             }
 
-            let mut pkcs8_bytes_ptr = MaybeUninit::<*mut u8>::uninit();
-            let mut out_len = MaybeUninit::<usize>::uninit();
-            if 1 != CBB_finish(
-                cbb.as_mut_ptr(),
-                pkcs8_bytes_ptr.as_mut_ptr(),
-                out_len.as_mut_ptr(),
-            ) {
+            Ok(Document {
+                bytes: cbb.finish()?,
+            })
+        }
+    }
+
+    pub(crate) fn marshall_public_key(&self) -> Result<spki::Document, Unspecified> {
+        unsafe {
+            let mut cbb = cbb::build_CBB(PKCS8_DOCUMENT_MAX_LEN);
+            if 1 != EVP_marshal_public_key(cbb.as_mut_ptr(), **self) {
                 return Err(Unspecified);
             }
-            let pkcs8_bytes_ptr = LcPtr::new(pkcs8_bytes_ptr.assume_init())?;
-            let out_len = out_len.assume_init();
-
-            let bytes_slice = std::slice::from_raw_parts(*pkcs8_bytes_ptr, out_len);
-            let mut pkcs8_bytes = [0u8; PKCS8_DOCUMENT_MAX_LEN];
-            pkcs8_bytes[0..out_len].copy_from_slice(bytes_slice);
-
-            Ok(Document {
-                bytes: pkcs8_bytes,
-                len: out_len,
+            Ok(spki::Document {
+                bytes: cbb.finish()?,
             })
         }
     }
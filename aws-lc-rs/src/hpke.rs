@@ -0,0 +1,745 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Hybrid Public Key Encryption (HPKE).
+//!
+//! HPKE is specified in [RFC 9180](https://www.rfc-editor.org/rfc/rfc9180). A sender
+//! encapsulates a fresh shared secret to the recipient's public key, and both parties derive an
+//! AEAD context from it that can seal/open a sequence of messages and export further secrets.
+//!
+//! The KEM supported is `DHKEM(X25519, HKDF-SHA256)`, with `HKDF-SHA256` as the KDF and
+//! AES-128-GCM, AES-256-GCM or ChaCha20-Poly1305 as the AEAD. Only the base mode
+//! (`mode_base`) is supported; the underlying *AWS-LC* implementation does not provide the
+//! PSK modes.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::{aead, agreement, hpke};
+//!
+//! // The recipient publishes its public key.
+//! let recipient_key = hpke::RecipientPrivateKey::generate(&hpke::DHKEM_X25519_HKDF_SHA256)?;
+//! let recipient_public_key = recipient_key.compute_public_key()?;
+//!
+//! // The sender encapsulates to that key.
+//! let (mut sender, enc) = hpke::SenderContext::new(
+//!     &hpke::DHKEM_X25519_HKDF_SHA256,
+//!     &hpke::HKDF_SHA256,
+//!     &hpke::AES_128_GCM,
+//!     &agreement::UnparsedPublicKey::new(&agreement::X25519, recipient_public_key.as_ref()),
+//!     b"application info",
+//! )?;
+//! let mut in_out = Vec::from(&b"hello, world"[..]);
+//! sender.seal_in_place_append_tag(aead::Aad::empty(), &mut in_out)?;
+//!
+//! // The recipient receives `enc` along with the ciphertext.
+//! let mut recipient = hpke::RecipientContext::new(
+//!     &hpke::HKDF_SHA256,
+//!     &hpke::AES_128_GCM,
+//!     &recipient_key,
+//!     enc.as_ref(),
+//!     b"application info",
+//! )?;
+//! let plaintext = recipient.open_in_place(aead::Aad::empty(), &mut in_out)?;
+//! assert_eq!(&b"hello, world"[..], plaintext);
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+
+use crate::aead::Aad;
+use crate::error::{KeyRejected, Unspecified};
+use crate::ptr::LcPtr;
+use crate::{agreement, derive_debug_via_id};
+use aws_lc::{
+    EVP_HPKE_CTX_export, EVP_HPKE_CTX_max_overhead, EVP_HPKE_CTX_new, EVP_HPKE_CTX_open,
+    EVP_HPKE_CTX_seal, EVP_HPKE_CTX_setup_recipient, EVP_HPKE_CTX_setup_sender,
+    EVP_HPKE_KEY_generate, EVP_HPKE_KEY_init, EVP_HPKE_KEY_new, EVP_HPKE_KEY_public_key,
+    EVP_hpke_aes_128_gcm, EVP_hpke_aes_256_gcm, EVP_hpke_chacha20_poly1305, EVP_hpke_hkdf_sha256,
+    EVP_hpke_x25519_hkdf_sha256, EVP_HPKE_AEAD, EVP_HPKE_CTX, EVP_HPKE_KDF, EVP_HPKE_KEM,
+    EVP_HPKE_KEY,
+};
+use std::fmt::{Debug, Formatter};
+use std::mem::MaybeUninit;
+
+/// The maximum length of an encapsulated key.
+const MAX_ENC_LEN: usize = aws_lc::EVP_HPKE_MAX_ENC_LENGTH as usize;
+
+const MAX_PUBLIC_KEY_LEN: usize = aws_lc::EVP_HPKE_MAX_PUBLIC_KEY_LENGTH as usize;
+
+/// An HPKE key encapsulation mechanism (KEM).
+pub struct Kem {
+    id: KemId,
+    evp: unsafe extern "C" fn() -> *const EVP_HPKE_KEM,
+    agreement_algorithm: &'static agreement::Algorithm,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum KemId {
+    DHKEM_X25519_HKDF_SHA256,
+}
+
+impl Kem {
+    /// The key agreement algorithm of the KEM's public keys.
+    #[inline]
+    #[must_use]
+    pub fn agreement_algorithm(&self) -> &'static agreement::Algorithm {
+        self.agreement_algorithm
+    }
+}
+
+derive_debug_via_id!(Kem);
+
+impl PartialEq for Kem {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Kem {}
+
+/// `DHKEM(X25519, HKDF-SHA256)`, interoperable with `agreement::X25519` public keys.
+pub static DHKEM_X25519_HKDF_SHA256: Kem = Kem {
+    id: KemId::DHKEM_X25519_HKDF_SHA256,
+    evp: EVP_hpke_x25519_hkdf_sha256,
+    agreement_algorithm: &agreement::X25519,
+};
+
+/// An HPKE key derivation function (KDF).
+pub struct Kdf {
+    id: KdfId,
+    evp: unsafe extern "C" fn() -> *const EVP_HPKE_KDF,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum KdfId {
+    HKDF_SHA256,
+}
+
+derive_debug_via_id!(Kdf);
+
+impl PartialEq for Kdf {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Kdf {}
+
+/// `HKDF-SHA256`.
+pub static HKDF_SHA256: Kdf = Kdf {
+    id: KdfId::HKDF_SHA256,
+    evp: EVP_hpke_hkdf_sha256,
+};
+
+/// An HPKE authenticated encryption (AEAD) algorithm.
+pub struct Aead {
+    id: AeadId,
+    evp: unsafe extern "C" fn() -> *const EVP_HPKE_AEAD,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AeadId {
+    AES_128_GCM,
+    AES_256_GCM,
+    CHACHA20_POLY1305,
+}
+
+derive_debug_via_id!(Aead);
+
+impl PartialEq for Aead {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Aead {}
+
+/// AES-128 in GCM mode.
+pub static AES_128_GCM: Aead = Aead {
+    id: AeadId::AES_128_GCM,
+    evp: EVP_hpke_aes_128_gcm,
+};
+
+/// AES-256 in GCM mode.
+pub static AES_256_GCM: Aead = Aead {
+    id: AeadId::AES_256_GCM,
+    evp: EVP_hpke_aes_256_gcm,
+};
+
+/// ChaCha20-Poly1305 as described in [RFC 8439](https://www.rfc-editor.org/rfc/rfc8439).
+pub static CHACHA20_POLY1305: Aead = Aead {
+    id: AeadId::CHACHA20_POLY1305,
+    evp: EVP_hpke_chacha20_poly1305,
+};
+
+/// A recipient's long-term private key for HPKE.
+pub struct RecipientPrivateKey {
+    key: LcPtr<*mut EVP_HPKE_KEY>,
+    kem: &'static Kem,
+}
+
+// An `EVP_HPKE_KEY` is not modified after it is initialized.
+unsafe impl Send for RecipientPrivateKey {}
+unsafe impl Sync for RecipientPrivateKey {}
+
+impl RecipientPrivateKey {
+    /// Generates a new private key for the given KEM.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn generate(kem: &'static Kem) -> Result<Self, Unspecified> {
+        let key = LcPtr::new(unsafe { EVP_HPKE_KEY_new() })?;
+        if 1 != unsafe { EVP_HPKE_KEY_generate(*key, (kem.evp)()) } {
+            return Err(Unspecified);
+        }
+        Ok(Self { key, kem })
+    }
+
+    /// Constructs a private key for the given KEM from its serialized form.
+    ///
+    /// For `DHKEM_X25519_HKDF_SHA256` this is the 32-byte X25519 scalar.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `private_key` is not a valid private key for `kem`.
+    pub fn from_private_key(kem: &'static Kem, private_key: &[u8]) -> Result<Self, KeyRejected> {
+        let key = LcPtr::new(unsafe { EVP_HPKE_KEY_new() })
+            .map_err(|()| KeyRejected::unexpected_error())?;
+        if 1 != unsafe {
+            EVP_HPKE_KEY_init(*key, (kem.evp)(), private_key.as_ptr(), private_key.len())
+        } {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Ok(Self { key, kem })
+    }
+
+    /// Computes the public key corresponding to this private key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn compute_public_key(&self) -> Result<agreement::PublicKey, Unspecified> {
+        let mut buffer = [0u8; MAX_PUBLIC_KEY_LEN];
+        let mut out_len = MaybeUninit::<usize>::uninit();
+        if 1 != unsafe {
+            EVP_HPKE_KEY_public_key(
+                *self.key,
+                buffer.as_mut_ptr(),
+                out_len.as_mut_ptr(),
+                buffer.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        let out_len = unsafe { out_len.assume_init() };
+        agreement::PublicKey::new(self.kem.agreement_algorithm, &buffer[..out_len])
+    }
+
+    /// The KEM for the private key.
+    #[inline]
+    #[must_use]
+    pub fn kem(&self) -> &'static Kem {
+        self.kem
+    }
+}
+
+impl Debug for RecipientPrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.write_str(&format!("RecipientPrivateKey {{ kem: {:?} }}", self.kem))
+    }
+}
+
+/// The encapsulated key (`enc`) produced by the sender, to be sent to the recipient.
+#[derive(Clone)]
+pub struct EncapsulatedKey {
+    bytes: [u8; MAX_ENC_LEN],
+    len: usize,
+}
+
+impl EncapsulatedKey {
+    fn new() -> Self {
+        Self {
+            bytes: [0u8; MAX_ENC_LEN],
+            len: 0,
+        }
+    }
+}
+
+impl AsRef<[u8]> for EncapsulatedKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl Debug for EncapsulatedKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.write_str(&format!(
+            "EncapsulatedKey {{ bytes: \"{}\" }}",
+            crate::test::to_hex(self.as_ref())
+        ))
+    }
+}
+
+/// The sender's HPKE context.
+///
+/// Messages are sealed with an implicit sequence number, so they must be opened by the
+/// recipient in the order they were sealed.
+pub struct SenderContext {
+    ctx: LcPtr<*mut EVP_HPKE_CTX>,
+    aead: &'static Aead,
+}
+
+// An `EVP_HPKE_CTX` is only modified through `&mut self`.
+unsafe impl Send for SenderContext {}
+unsafe impl Sync for SenderContext {}
+
+impl SenderContext {
+    /// Sets up a base mode context that encrypts to `recipient_public_key`.
+    ///
+    /// The returned `EncapsulatedKey` must be provided to the recipient along with `info`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `recipient_public_key` is not a valid public key for `kem`, or
+    /// when operation fails due to internal error.
+    pub fn new<B: AsRef<[u8]>>(
+        kem: &'static Kem,
+        kdf: &'static Kdf,
+        aead: &'static Aead,
+        recipient_public_key: &agreement::UnparsedPublicKey<B>,
+        info: &[u8],
+    ) -> Result<(Self, EncapsulatedKey), Unspecified> {
+        if recipient_public_key.algorithm() != kem.agreement_algorithm {
+            return Err(Unspecified);
+        }
+        let peer_public_key = recipient_public_key.bytes().as_ref();
+        let ctx = LcPtr::new(unsafe { EVP_HPKE_CTX_new() })?;
+        let mut enc = EncapsulatedKey::new();
+
+        if 1 != unsafe {
+            EVP_HPKE_CTX_setup_sender(
+                *ctx,
+                enc.bytes.as_mut_ptr(),
+                &mut enc.len,
+                enc.bytes.len(),
+                (kem.evp)(),
+                (kdf.evp)(),
+                (aead.evp)(),
+                peer_public_key.as_ptr(),
+                peer_public_key.len(),
+                info.as_ptr(),
+                info.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok((Self { ctx, aead }, enc))
+    }
+
+    // Uses `seed` as the sender's ephemeral private key, so that test vectors can be reproduced.
+    #[cfg(test)]
+    fn new_with_seed_for_test<B: AsRef<[u8]>>(
+        kem: &'static Kem,
+        kdf: &'static Kdf,
+        aead: &'static Aead,
+        recipient_public_key: &agreement::UnparsedPublicKey<B>,
+        info: &[u8],
+        seed: &[u8],
+    ) -> Result<(Self, EncapsulatedKey), Unspecified> {
+        if recipient_public_key.algorithm() != kem.agreement_algorithm {
+            return Err(Unspecified);
+        }
+        let peer_public_key = recipient_public_key.bytes().as_ref();
+        let ctx = LcPtr::new(unsafe { EVP_HPKE_CTX_new() })?;
+        let mut enc = EncapsulatedKey::new();
+
+        if 1 != unsafe {
+            aws_lc::EVP_HPKE_CTX_setup_sender_with_seed_for_testing(
+                *ctx,
+                enc.bytes.as_mut_ptr(),
+                &mut enc.len,
+                enc.bytes.len(),
+                (kem.evp)(),
+                (kdf.evp)(),
+                (aead.evp)(),
+                peer_public_key.as_ptr(),
+                peer_public_key.len(),
+                info.as_ptr(),
+                info.len(),
+                seed.as_ptr(),
+                seed.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok((Self { ctx, aead }, enc))
+    }
+
+    /// Encrypts and signs (“seals”) the next message in place, appending the tag to the
+    /// resulting ciphertext.
+    ///
+    /// `aad` is the additional authenticated data (AAD), if any.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the message sequence number is exhausted, or when operation
+    /// fails due to internal error.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn seal_in_place_append_tag<A, InOut>(
+        &mut self,
+        aad: Aad<A>,
+        in_out: &mut InOut,
+    ) -> Result<(), Unspecified>
+    where
+        A: AsRef<[u8]>,
+        InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
+    {
+        let aad = aad.as_ref();
+        let plaintext_len = in_out.as_mut().len();
+        let overhead = unsafe { EVP_HPKE_CTX_max_overhead(*self.ctx) };
+        in_out.extend([0u8; aws_lc::EVP_HPKE_MAX_OVERHEAD as usize][..overhead].iter());
+
+        let in_out = in_out.as_mut();
+        let mut out_len = MaybeUninit::<usize>::uninit();
+        if 1 != unsafe {
+            EVP_HPKE_CTX_seal(
+                *self.ctx,
+                in_out.as_mut_ptr(),
+                out_len.as_mut_ptr(),
+                in_out.len(),
+                in_out.as_ptr(),
+                plaintext_len,
+                aad.as_ptr(),
+                aad.len(),
+            )
+        } || unsafe { out_len.assume_init() } != in_out.len()
+        {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// Exports a secret of `out.len()` bytes bound to `exporter_context`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `out` is longer than the KDF permits.
+    pub fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), Unspecified> {
+        export(&self.ctx, exporter_context, out)
+    }
+
+    /// The context's AEAD algorithm.
+    #[inline]
+    #[must_use]
+    pub fn aead(&self) -> &'static Aead {
+        self.aead
+    }
+}
+
+impl Debug for SenderContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.write_str(&format!("SenderContext {{ aead: {:?} }}", self.aead))
+    }
+}
+
+/// The recipient's HPKE context.
+pub struct RecipientContext {
+    ctx: LcPtr<*mut EVP_HPKE_CTX>,
+    aead: &'static Aead,
+}
+
+// An `EVP_HPKE_CTX` is only modified through `&mut self`.
+unsafe impl Send for RecipientContext {}
+unsafe impl Sync for RecipientContext {}
+
+impl RecipientContext {
+    /// Sets up a base mode context from the sender's encapsulated key `enc`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `enc` is invalid, or when operation fails due to internal error.
+    pub fn new(
+        kdf: &'static Kdf,
+        aead: &'static Aead,
+        private_key: &RecipientPrivateKey,
+        enc: &[u8],
+        info: &[u8],
+    ) -> Result<Self, Unspecified> {
+        let ctx = LcPtr::new(unsafe { EVP_HPKE_CTX_new() })?;
+        if 1 != unsafe {
+            EVP_HPKE_CTX_setup_recipient(
+                *ctx,
+                *private_key.key,
+                (kdf.evp)(),
+                (aead.evp)(),
+                enc.as_ptr(),
+                enc.len(),
+                info.as_ptr(),
+                info.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(Self { ctx, aead })
+    }
+
+    /// Authenticates and decrypts (“opens”) the next message in place.
+    ///
+    /// On input, `in_out` must be the ciphertext followed by the tag. When
+    /// `open_in_place()` returns `Ok(plaintext)`, the input ciphertext
+    /// has been overwritten by the plaintext; `plaintext` will refer to the
+    /// plaintext without the tag.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when ciphertext is invalid. In this case, `in_out` may have been
+    /// overwritten in an unspecified way.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn open_in_place<'in_out, A>(
+        &mut self,
+        aad: Aad<A>,
+        in_out: &'in_out mut [u8],
+    ) -> Result<&'in_out mut [u8], Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        let aad = aad.as_ref();
+        let mut out_len = MaybeUninit::<usize>::uninit();
+        if 1 != unsafe {
+            EVP_HPKE_CTX_open(
+                *self.ctx,
+                in_out.as_mut_ptr(),
+                out_len.as_mut_ptr(),
+                in_out.len(),
+                in_out.as_ptr(),
+                in_out.len(),
+                aad.as_ptr(),
+                aad.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        let out_len = unsafe { out_len.assume_init() };
+        Ok(&mut in_out[..out_len])
+    }
+
+    /// Exports a secret of `out.len()` bytes bound to `exporter_context`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `out` is longer than the KDF permits.
+    pub fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), Unspecified> {
+        export(&self.ctx, exporter_context, out)
+    }
+
+    /// The context's AEAD algorithm.
+    #[inline]
+    #[must_use]
+    pub fn aead(&self) -> &'static Aead {
+        self.aead
+    }
+}
+
+impl Debug for RecipientContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.write_str(&format!("RecipientContext {{ aead: {:?} }}", self.aead))
+    }
+}
+
+fn export(
+    ctx: &LcPtr<*mut EVP_HPKE_CTX>,
+    exporter_context: &[u8],
+    out: &mut [u8],
+) -> Result<(), Unspecified> {
+    if 1 != unsafe {
+        EVP_HPKE_CTX_export(
+            **ctx,
+            out.as_mut_ptr(),
+            out.len(),
+            exporter_context.as_ptr(),
+            exporter_context.len(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aead::Aad;
+    use crate::hpke::{
+        Aead, RecipientContext, RecipientPrivateKey, SenderContext, AES_128_GCM, AES_256_GCM,
+        CHACHA20_POLY1305, DHKEM_X25519_HKDF_SHA256, HKDF_SHA256,
+    };
+    use crate::{agreement, test, test_file};
+
+    fn aead_from_name(name: &str) -> &'static Aead {
+        match name {
+            "AES_128_GCM" => &AES_128_GCM,
+            "AES_256_GCM" => &AES_256_GCM,
+            "CHACHA20_POLY1305" => &CHACHA20_POLY1305,
+            _ => panic!("Unsupported AEAD: {name}"),
+        }
+    }
+
+    #[test]
+    fn hpke_base_mode_tests() {
+        test::run(test_file!("data/hpke_tests.txt"), |section, test_case| {
+            assert_eq!(section, "");
+            let aead_alg = aead_from_name(&test_case.consume_string("AEAD"));
+            let info = test_case.consume_bytes("Info");
+            let ephemeral_seed = test_case.consume_bytes("SkEm");
+            let expected_enc = test_case.consume_bytes("Enc");
+            let recipient_private = test_case.consume_bytes("SkRm");
+            let recipient_public = test_case.consume_bytes("PkRm");
+            let seq = test_case.consume_usize("Seq");
+            let aad = test_case.consume_bytes("AAD");
+            let plaintext = test_case.consume_bytes("PT");
+            let ciphertext = test_case.consume_bytes("CT");
+            let exporter_context = test_case.consume_bytes("ExporterContext");
+            let exported = test_case.consume_bytes("Exported");
+
+            let recipient_key = RecipientPrivateKey::from_private_key(
+                &DHKEM_X25519_HKDF_SHA256,
+                &recipient_private,
+            )
+            .unwrap();
+            let public_key = recipient_key.compute_public_key().unwrap();
+            assert_eq!(&recipient_public[..], public_key.as_ref());
+
+            let (mut sender, enc) = SenderContext::new_with_seed_for_test(
+                &DHKEM_X25519_HKDF_SHA256,
+                &HKDF_SHA256,
+                aead_alg,
+                &agreement::UnparsedPublicKey::new(&agreement::X25519, &recipient_public),
+                &info,
+                &ephemeral_seed,
+            )
+            .unwrap();
+            assert_eq!(&expected_enc[..], enc.as_ref());
+
+            let mut recipient =
+                RecipientContext::new(&HKDF_SHA256, aead_alg, &recipient_key, enc.as_ref(), &info)
+                    .unwrap();
+
+            // Advance both contexts to the sequence number of the test case.
+            for _ in 0..seq {
+                let mut in_out = Vec::new();
+                sender
+                    .seal_in_place_append_tag(Aad::empty(), &mut in_out)
+                    .unwrap();
+                recipient.open_in_place(Aad::empty(), &mut in_out).unwrap();
+            }
+
+            let mut in_out = plaintext.clone();
+            sender
+                .seal_in_place_append_tag(Aad::from(&aad), &mut in_out)
+                .unwrap();
+            assert_eq!(ciphertext, in_out);
+            let opened = recipient
+                .open_in_place(Aad::from(&aad), &mut in_out)
+                .unwrap();
+            assert_eq!(&plaintext[..], opened);
+
+            let mut sender_exported = vec![0u8; exported.len()];
+            sender
+                .export(&exporter_context, &mut sender_exported)
+                .unwrap();
+            assert_eq!(exported, sender_exported);
+            let mut recipient_exported = vec![0u8; exported.len()];
+            recipient
+                .export(&exporter_context, &mut recipient_exported)
+                .unwrap();
+            assert_eq!(exported, recipient_exported);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn hpke_rejects_wrong_inputs() {
+        let recipient_key = RecipientPrivateKey::generate(&DHKEM_X25519_HKDF_SHA256).unwrap();
+        let public_key = recipient_key.compute_public_key().unwrap();
+        let (mut sender, enc) = SenderContext::new(
+            &DHKEM_X25519_HKDF_SHA256,
+            &HKDF_SHA256,
+            &CHACHA20_POLY1305,
+            &agreement::UnparsedPublicKey::new(&agreement::X25519, public_key.as_ref()),
+            b"info",
+        )
+        .unwrap();
+        let mut in_out = Vec::from(&b"message"[..]);
+        sender
+            .seal_in_place_append_tag(Aad::from(b"aad"), &mut in_out)
+            .unwrap();
+
+        // Different info
+        let mut recipient = RecipientContext::new(
+            &HKDF_SHA256,
+            &CHACHA20_POLY1305,
+            &recipient_key,
+            enc.as_ref(),
+            b"other info",
+        )
+        .unwrap();
+        assert!(recipient
+            .open_in_place(Aad::from(b"aad"), &mut in_out.clone())
+            .is_err());
+
+        // Different AAD
+        let mut recipient = RecipientContext::new(
+            &HKDF_SHA256,
+            &CHACHA20_POLY1305,
+            &recipient_key,
+            enc.as_ref(),
+            b"info",
+        )
+        .unwrap();
+        assert!(recipient
+            .open_in_place(Aad::from(b"other aad"), &mut in_out.clone())
+            .is_err());
+
+        // Malformed encapsulated key
+        assert!(RecipientContext::new(
+            &HKDF_SHA256,
+            &CHACHA20_POLY1305,
+            &recipient_key,
+            &enc.as_ref()[1..],
+            b"info",
+        )
+        .is_err());
+
+        // Public key of another algorithm
+        assert!(SenderContext::new(
+            &DHKEM_X25519_HKDF_SHA256,
+            &HKDF_SHA256,
+            &AES_128_GCM,
+            &agreement::UnparsedPublicKey::new(&agreement::ECDH_P256, public_key.as_ref()),
+            b"info",
+        )
+        .is_err());
+
+        // Private key of the wrong length
+        assert!(
+            RecipientPrivateKey::from_private_key(&DHKEM_X25519_HKDF_SHA256, &[0u8; 31]).is_err()
+        );
+    }
+
+    #[test]
+    fn hpke_debug() {
+        let key = RecipientPrivateKey::generate(&DHKEM_X25519_HKDF_SHA256).unwrap();
+        assert_eq!(
+            "RecipientPrivateKey { kem: DHKEM_X25519_HKDF_SHA256 }",
+            format!("{key:?}")
+        );
+        assert_eq!("AES_256_GCM", format!("{AES_256_GCM:?}"));
+        assert_eq!("HKDF_SHA256", format!("{HKDF_SHA256:?}"));
+    }
+
+    #[test]
+    fn hpke_types() {
+        test::compile_time_assert_send::<RecipientPrivateKey>();
+        test::compile_time_assert_sync::<RecipientPrivateKey>();
+        test::compile_time_assert_send::<SenderContext>();
+        test::compile_time_assert_send::<RecipientContext>();
+    }
+}
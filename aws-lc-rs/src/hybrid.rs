@@ -0,0 +1,360 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Hybrid key agreement combining X25519 with Kyber, for post-quantum TLS key exchange.
+//!
+//! A hybrid key agreement remains secure as long as at least one of its components is
+//! secure. The key shares and shared secret are the concatenation of the classical and
+//! post-quantum components, in the layout used by the IETF hybrid drafts:
+//!
+//! * The initiator's key share is its X25519 public key followed by its Kyber encapsulation
+//!   key.
+//! * The responder's key share is its X25519 public key followed by the Kyber ciphertext.
+//! * The shared secret is the X25519 shared secret followed by the Kyber shared secret.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::{error::Unspecified, hybrid};
+//!
+//! // The client generates an ephemeral key and sends its public key share.
+//! let client_private_key = hybrid::PrivateKey::generate(&hybrid::X25519_KYBER768_DRAFT00)?;
+//! let client_share = client_private_key.compute_public_key_share()?;
+//!
+//! // The server responds with a ciphertext share and obtains the shared secret.
+//! let (server_share, server_secret) =
+//!     hybrid::encapsulate(&hybrid::X25519_KYBER768_DRAFT00, client_share.as_ref())?;
+//!
+//! // The client obtains the same shared secret from the server's share.
+//! hybrid::agree_ephemeral(
+//!     client_private_key,
+//!     server_share.as_ref(),
+//!     Unspecified,
+//!     |client_secret| {
+//!         assert_eq!(server_secret.as_ref(), client_secret);
+//!         // In a real application, we'd derive session keys from the shared secret.
+//!         Ok(())
+//!     },
+//! )?;
+//!
+//! # Ok::<(), Unspecified>(())
+//! ```
+
+use crate::agreement::{self, EphemeralPrivateKey, UnparsedPublicKey};
+use crate::error::Unspecified;
+use crate::kem::{self, DecapsulationKey, EncapsulationKey, SharedSecret};
+use crate::{derive_debug_via_id, rand};
+use core::fmt;
+use std::fmt::{Debug, Formatter};
+
+const X25519_PUBLIC_VALUE_LEN: usize = aws_lc::X25519_PUBLIC_VALUE_LEN as usize;
+const X25519_SHARED_KEY_LEN: usize = aws_lc::X25519_SHARED_KEY_LEN as usize;
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AlgorithmID {
+    X25519_KYBER768_DRAFT00,
+}
+
+/// A hybrid key agreement algorithm.
+pub struct Algorithm {
+    id: AlgorithmID,
+    kem: &'static kem::Algorithm,
+}
+
+impl Algorithm {
+    /// The length in bytes of the initiator's public key share.
+    #[inline]
+    #[must_use]
+    pub fn public_key_share_len(&self) -> usize {
+        X25519_PUBLIC_VALUE_LEN + self.kem.encapsulation_key_len()
+    }
+
+    /// The length in bytes of the responder's ciphertext share.
+    #[inline]
+    #[must_use]
+    pub fn ciphertext_share_len(&self) -> usize {
+        X25519_PUBLIC_VALUE_LEN + self.kem.ciphertext_len()
+    }
+
+    /// The length in bytes of the combined shared secret.
+    #[inline]
+    #[must_use]
+    pub fn shared_secret_len(&self) -> usize {
+        X25519_SHARED_KEY_LEN + self.kem.shared_secret_len()
+    }
+}
+
+derive_debug_via_id!(Algorithm);
+
+impl PartialEq for Algorithm {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Algorithm {}
+
+/// X25519 combined with Kyber-768 (Round 3), as specified by
+/// draft-tls-westerbaan-xyber768d00 for the TLS `X25519Kyber768Draft00` group.
+pub static X25519_KYBER768_DRAFT00: Algorithm = Algorithm {
+    id: AlgorithmID::X25519_KYBER768_DRAFT00,
+    kem: &kem::KYBER768_R3,
+};
+
+/// An ephemeral hybrid private key for use (only) with `agree_ephemeral`.
+pub struct PrivateKey {
+    algorithm: &'static Algorithm,
+    x25519_key: EphemeralPrivateKey,
+    kem_key: DecapsulationKey,
+}
+
+impl PrivateKey {
+    /// Generates a new ephemeral private key for the given algorithm.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn generate(algorithm: &'static Algorithm) -> Result<Self, Unspecified> {
+        let x25519_key =
+            EphemeralPrivateKey::generate(&agreement::X25519, &rand::SystemRandom::new())?;
+        let kem_key = DecapsulationKey::generate(algorithm.kem)?;
+        Ok(Self {
+            algorithm,
+            x25519_key,
+            kem_key,
+        })
+    }
+
+    #[cfg(test)]
+    fn from_components_for_test(
+        algorithm: &'static Algorithm,
+        x25519_private_key: &[u8],
+        kem_public_key: &[u8],
+        kem_secret_key: &[u8],
+    ) -> Result<Self, Unspecified> {
+        let x25519_key = EphemeralPrivateKey::generate_for_test(
+            &agreement::X25519,
+            &crate::test::rand::FixedSliceRandom {
+                bytes: x25519_private_key,
+            },
+        )?;
+        let kem_key = DecapsulationKey::from_raw_keys_for_test(
+            algorithm.kem,
+            kem_public_key,
+            kem_secret_key,
+        )?;
+        Ok(Self {
+            algorithm,
+            x25519_key,
+            kem_key,
+        })
+    }
+
+    /// Computes the public key share to be sent to the peer.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn compute_public_key_share(&self) -> Result<PublicKeyShare, Unspecified> {
+        let x25519_public_key = self.x25519_key.compute_public_key()?;
+        let encapsulation_key = self.kem_key.encapsulation_key()?.key_bytes()?;
+
+        let mut share = Vec::with_capacity(self.algorithm.public_key_share_len());
+        share.extend_from_slice(x25519_public_key.as_ref());
+        share.extend_from_slice(&encapsulation_key);
+        Ok(PublicKeyShare(share.into_boxed_slice()))
+    }
+
+    /// The algorithm for the private key.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+}
+
+impl Debug for PrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!("PrivateKey {{ algorithm: {:?} }}", self.algorithm))
+    }
+}
+
+/// The initiator's public key share: its X25519 public key followed by its Kyber
+/// encapsulation key.
+#[derive(Clone)]
+pub struct PublicKeyShare(Box<[u8]>);
+
+impl AsRef<[u8]> for PublicKeyShare {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl Debug for PublicKeyShare {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "PublicKeyShare(\"{}\")",
+            crate::test::to_hex(self.0.as_ref())
+        ))
+    }
+}
+
+/// The responder's ciphertext share: its X25519 public key followed by the Kyber
+/// ciphertext.
+#[derive(Clone)]
+pub struct CiphertextShare(Box<[u8]>);
+
+impl AsRef<[u8]> for CiphertextShare {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl Debug for CiphertextShare {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "CiphertextShare(\"{}\")",
+            crate::test::to_hex(self.0.as_ref())
+        ))
+    }
+}
+
+/// Responds to the peer's public key share, returning the ciphertext share to be sent
+/// to the peer along with the combined shared secret.
+///
+/// # Errors
+/// `error::Unspecified` if `peer_public_key_share` is not a valid public key share for
+/// `algorithm`, or when operation fails due to internal error.
+pub fn encapsulate(
+    algorithm: &'static Algorithm,
+    peer_public_key_share: &[u8],
+) -> Result<(CiphertextShare, SharedSecret), Unspecified> {
+    if peer_public_key_share.len() != algorithm.public_key_share_len() {
+        return Err(Unspecified);
+    }
+    let (peer_x25519_public_key, peer_encapsulation_key) =
+        peer_public_key_share.split_at(X25519_PUBLIC_VALUE_LEN);
+
+    let encapsulation_key =
+        EncapsulationKey::new(algorithm.kem, peer_encapsulation_key).map_err(|_| Unspecified)?;
+    let (ciphertext, kem_secret) = encapsulation_key.encapsulate()?;
+
+    let x25519_key = EphemeralPrivateKey::generate(&agreement::X25519, &rand::SystemRandom::new())?;
+    let x25519_public_key = x25519_key.compute_public_key()?;
+
+    let mut shared_secret = Vec::with_capacity(algorithm.shared_secret_len());
+    agreement::agree_ephemeral(
+        x25519_key,
+        &UnparsedPublicKey::new(&agreement::X25519, peer_x25519_public_key),
+        Unspecified,
+        |x25519_secret| {
+            shared_secret.extend_from_slice(x25519_secret);
+            Ok(())
+        },
+    )?;
+    shared_secret.extend_from_slice(kem_secret.as_ref());
+
+    let mut share = Vec::with_capacity(algorithm.ciphertext_share_len());
+    share.extend_from_slice(x25519_public_key.as_ref());
+    share.extend_from_slice(ciphertext.as_ref());
+
+    Ok((
+        CiphertextShare(share.into_boxed_slice()),
+        SharedSecret::new(shared_secret.into_boxed_slice()),
+    ))
+}
+
+/// Performs a hybrid key agreement with an ephemeral private key and the peer's
+/// ciphertext share.
+///
+/// `my_private_key` is consumed, guaranteeing that it is used for only one key agreement.
+///
+/// `error_value` is the value to return if an error occurs before `kdf` is called, e.g.
+/// when `peer_ciphertext_share` does not have the length required by the algorithm or
+/// its X25519 public key is invalid.
+///
+/// After the key agreement is done, `agree_ephemeral` calls `kdf` with the combined
+/// shared secret and then returns what `kdf` returns.
+///
+/// # Errors
+/// `error_value` on internal failure.
+#[allow(clippy::needless_pass_by_value)]
+pub fn agree_ephemeral<F, R, E>(
+    my_private_key: PrivateKey,
+    peer_ciphertext_share: &[u8],
+    error_value: E,
+    kdf: F,
+) -> Result<R, E>
+where
+    F: FnOnce(&[u8]) -> Result<R, E>,
+{
+    let algorithm = my_private_key.algorithm;
+    if peer_ciphertext_share.len() != algorithm.ciphertext_share_len() {
+        return Err(error_value);
+    }
+    let (peer_x25519_public_key, ciphertext) =
+        peer_ciphertext_share.split_at(X25519_PUBLIC_VALUE_LEN);
+
+    let mut shared_secret = Vec::with_capacity(algorithm.shared_secret_len());
+    let result = agreement::agree_ephemeral(
+        my_private_key.x25519_key,
+        &UnparsedPublicKey::new(&agreement::X25519, peer_x25519_public_key),
+        Unspecified,
+        |x25519_secret| {
+            shared_secret.extend_from_slice(x25519_secret);
+            Ok(())
+        },
+    )
+    .and_then(|()| {
+        my_private_key
+            .kem_key
+            .decapsulate(ciphertext, Unspecified, |kem_secret| {
+                shared_secret.extend_from_slice(kem_secret);
+                Ok(())
+            })
+    });
+    let shared_secret = SharedSecret::new(shared_secret.into_boxed_slice());
+    match result {
+        Ok(()) => kdf(shared_secret.as_ref()),
+        Err(Unspecified) => Err(error_value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Unspecified;
+    use crate::hybrid::{self, PrivateKey, X25519_KYBER768_DRAFT00};
+    use crate::{test, test_file};
+
+    #[test]
+    fn test_hybrid_vectors() {
+        test::run(test_file!("data/hybrid_tests.txt"), |section, test_case| {
+            assert_eq!(section, "");
+            let algorithm = &X25519_KYBER768_DRAFT00;
+            let x25519_private_key = test_case.consume_bytes("X25519PrivateKey");
+            let kem_secret_key = test_case.consume_bytes("KyberSecretKey");
+            let public_key_share = test_case.consume_bytes("PublicKeyShare");
+            let ciphertext_share = test_case.consume_bytes("CiphertextShare");
+            let shared_secret = test_case.consume_bytes("SharedSecret");
+
+            let private_key = PrivateKey::from_components_for_test(
+                algorithm,
+                &x25519_private_key,
+                &public_key_share[32..],
+                &kem_secret_key,
+            )?;
+            assert_eq!(
+                &public_key_share[..],
+                private_key.compute_public_key_share()?.as_ref()
+            );
+
+            hybrid::agree_ephemeral(private_key, &ciphertext_share, Unspecified, |secret| {
+                assert_eq!(&shared_secret[..], secret);
+                Ok(())
+            })?;
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,409 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Key-Encapsulation Mechanisms (KEMs), including Kyber.
+//!
+//! A KEM lets the holder of an encapsulation (public) key produce a fresh shared secret along
+//! with a ciphertext; only the holder of the matching decapsulation (private) key can recover
+//! the shared secret from the ciphertext.
+//!
+//! The Kyber algorithms provided are the NIST PQC Round 3 submissions.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::kem;
+//!
+//! // The decapsulating party generates a key and publishes the encapsulation key.
+//! let decapsulation_key = kem::DecapsulationKey::generate(&kem::KYBER768_R3)?;
+//! let encapsulation_key_bytes = decapsulation_key.encapsulation_key()?.key_bytes()?;
+//!
+//! // The encapsulating party produces a ciphertext and shared secret for that key.
+//! let encapsulation_key =
+//!     kem::EncapsulationKey::new(&kem::KYBER768_R3, &encapsulation_key_bytes).unwrap();
+//! let (ciphertext, alice_secret) = encapsulation_key.encapsulate()?;
+//!
+//! // The decapsulating party recovers the same shared secret from the ciphertext.
+//! decapsulation_key.decapsulate(
+//!     ciphertext.as_ref(),
+//!     aws_lc_rs::error::Unspecified,
+//!     |bob_secret| {
+//!         assert_eq!(alice_secret.as_ref(), bob_secret);
+//!         // In a real application, we'd derive session keys from the shared secret.
+//!         Ok(())
+//!     },
+//! )?;
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+
+use crate::error::{KeyRejected, Unspecified};
+use crate::ptr::LcPtr;
+use crate::{derive_debug_via_id, test};
+use aws_lc::{
+    EVP_PKEY_CTX_kem_set_params, EVP_PKEY_CTX_new, EVP_PKEY_CTX_new_id, EVP_PKEY_decapsulate,
+    EVP_PKEY_encapsulate, EVP_PKEY_get_raw_public_key, EVP_PKEY_kem_new_raw_public_key,
+    EVP_PKEY_keygen, EVP_PKEY_keygen_init, EVP_PKEY, EVP_PKEY_KEM, NID_KYBER1024_R3,
+    NID_KYBER512_R3, NID_KYBER768_R3,
+};
+use core::fmt;
+use std::fmt::{Debug, Formatter};
+use std::ptr::null_mut;
+use zeroize::Zeroize;
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AlgorithmID {
+    KYBER512_R3,
+    KYBER768_R3,
+    KYBER1024_R3,
+}
+
+impl AlgorithmID {
+    #[inline]
+    fn nid(self) -> i32 {
+        match self {
+            AlgorithmID::KYBER512_R3 => NID_KYBER512_R3,
+            AlgorithmID::KYBER768_R3 => NID_KYBER768_R3,
+            AlgorithmID::KYBER1024_R3 => NID_KYBER1024_R3,
+        }
+    }
+}
+
+/// A key-encapsulation mechanism algorithm.
+pub struct Algorithm {
+    id: AlgorithmID,
+    encapsulation_key_len: usize,
+    ciphertext_len: usize,
+    shared_secret_len: usize,
+}
+
+impl Algorithm {
+    /// The length in bytes of a serialized encapsulation key.
+    #[inline]
+    #[must_use]
+    pub fn encapsulation_key_len(&self) -> usize {
+        self.encapsulation_key_len
+    }
+
+    /// The length in bytes of a ciphertext.
+    #[inline]
+    #[must_use]
+    pub fn ciphertext_len(&self) -> usize {
+        self.ciphertext_len
+    }
+
+    /// The length in bytes of a shared secret.
+    #[inline]
+    #[must_use]
+    pub fn shared_secret_len(&self) -> usize {
+        self.shared_secret_len
+    }
+}
+
+derive_debug_via_id!(Algorithm);
+
+impl PartialEq for Algorithm {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Algorithm {}
+
+/// Kyber-512, NIST PQC Round 3.
+pub static KYBER512_R3: Algorithm = Algorithm {
+    id: AlgorithmID::KYBER512_R3,
+    encapsulation_key_len: 800,
+    ciphertext_len: 768,
+    shared_secret_len: 32,
+};
+
+/// Kyber-768, NIST PQC Round 3.
+pub static KYBER768_R3: Algorithm = Algorithm {
+    id: AlgorithmID::KYBER768_R3,
+    encapsulation_key_len: 1184,
+    ciphertext_len: 1088,
+    shared_secret_len: 32,
+};
+
+/// Kyber-1024, NIST PQC Round 3.
+pub static KYBER1024_R3: Algorithm = Algorithm {
+    id: AlgorithmID::KYBER1024_R3,
+    encapsulation_key_len: 1568,
+    ciphertext_len: 1568,
+    shared_secret_len: 32,
+};
+
+/// A private key used to decapsulate shared secrets.
+pub struct DecapsulationKey {
+    algorithm: &'static Algorithm,
+    evp_pkey: LcPtr<*mut EVP_PKEY>,
+}
+
+// An `EVP_PKEY` is not modified after it is generated.
+unsafe impl Send for DecapsulationKey {}
+unsafe impl Sync for DecapsulationKey {}
+
+impl DecapsulationKey {
+    /// Generates a new decapsulation key for the given algorithm.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn generate(algorithm: &'static Algorithm) -> Result<Self, Unspecified> {
+        let ctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new_id(EVP_PKEY_KEM, null_mut()) })?;
+        if 1 != unsafe { EVP_PKEY_CTX_kem_set_params(*ctx, algorithm.id.nid()) }
+            || 1 != unsafe { EVP_PKEY_keygen_init(*ctx) }
+        {
+            return Err(Unspecified);
+        }
+        let mut evp_pkey = null_mut::<EVP_PKEY>();
+        if 1 != unsafe { EVP_PKEY_keygen(*ctx, &mut evp_pkey) } {
+            return Err(Unspecified);
+        }
+        let evp_pkey = LcPtr::new(evp_pkey)?;
+        Ok(Self {
+            algorithm,
+            evp_pkey,
+        })
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_raw_keys_for_test(
+        algorithm: &'static Algorithm,
+        public_key: &[u8],
+        secret_key: &[u8],
+    ) -> Result<Self, Unspecified> {
+        let evp_pkey = LcPtr::new(unsafe {
+            aws_lc::EVP_PKEY_kem_new_raw_key(
+                algorithm.id.nid(),
+                public_key.as_ptr(),
+                public_key.len(),
+                secret_key.as_ptr(),
+                secret_key.len(),
+            )
+        })?;
+        Ok(Self {
+            algorithm,
+            evp_pkey,
+        })
+    }
+
+    /// Computes the encapsulation key corresponding to this decapsulation key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn encapsulation_key(&self) -> Result<EncapsulationKey, Unspecified> {
+        let key_bytes = raw_public_key(self.algorithm, &self.evp_pkey)?;
+        EncapsulationKey::new(self.algorithm, &key_bytes).map_err(|_| Unspecified)
+    }
+
+    /// Decapsulates the shared secret from `ciphertext`.
+    ///
+    /// `error_value` is the value to return if an error occurs before `kdf` is
+    /// called, e.g. when `ciphertext` does not have the length required by the
+    /// algorithm.
+    ///
+    /// After decapsulation, `decapsulate` calls `kdf` with the shared secret and then
+    /// returns what `kdf` returns. As with Kyber in general, a malformed ciphertext of
+    /// the correct length is not detected here; it yields a shared secret that will not
+    /// match the encapsulating party's.
+    ///
+    /// # Errors
+    /// `error_value` on internal failure.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn decapsulate<F, R, E>(&self, ciphertext: &[u8], error_value: E, kdf: F) -> Result<R, E>
+    where
+        F: FnOnce(&[u8]) -> Result<R, E>,
+    {
+        if ciphertext.len() != self.algorithm.ciphertext_len {
+            return Err(error_value);
+        }
+        let ctx = match LcPtr::new(unsafe { EVP_PKEY_CTX_new(*self.evp_pkey, null_mut()) }) {
+            Ok(ctx) => ctx,
+            Err(()) => return Err(error_value),
+        };
+
+        // `EVP_PKEY_decapsulate` does not take the ciphertext by const pointer.
+        let mut ciphertext = ciphertext.to_vec();
+        let mut shared_secret = SharedSecret(vec![0u8; self.algorithm.shared_secret_len].into());
+        let mut shared_secret_len = shared_secret.0.len();
+        if 1 != unsafe {
+            EVP_PKEY_decapsulate(
+                *ctx,
+                shared_secret.0.as_mut_ptr(),
+                &mut shared_secret_len,
+                ciphertext.as_mut_ptr(),
+                ciphertext.len(),
+            )
+        } || shared_secret_len != self.algorithm.shared_secret_len
+        {
+            return Err(error_value);
+        }
+        kdf(shared_secret.as_ref())
+    }
+
+    /// The algorithm for the key.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+}
+
+impl Debug for DecapsulationKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "DecapsulationKey {{ algorithm: {:?} }}",
+            self.algorithm
+        ))
+    }
+}
+
+/// A public key used to encapsulate shared secrets.
+pub struct EncapsulationKey {
+    algorithm: &'static Algorithm,
+    evp_pkey: LcPtr<*mut EVP_PKEY>,
+}
+
+// An `EVP_PKEY` is not modified after it is constructed.
+unsafe impl Send for EncapsulationKey {}
+unsafe impl Sync for EncapsulationKey {}
+
+impl EncapsulationKey {
+    /// Constructs an encapsulation key from its serialized form.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `key_bytes` is not a valid encapsulation key for `algorithm`.
+    pub fn new(algorithm: &'static Algorithm, key_bytes: &[u8]) -> Result<Self, KeyRejected> {
+        if key_bytes.len() < algorithm.encapsulation_key_len {
+            return Err(KeyRejected::too_small());
+        }
+        if key_bytes.len() > algorithm.encapsulation_key_len {
+            return Err(KeyRejected::too_large());
+        }
+        let evp_pkey = LcPtr::new(unsafe {
+            EVP_PKEY_kem_new_raw_public_key(algorithm.id.nid(), key_bytes.as_ptr(), key_bytes.len())
+        })
+        .map_err(|()| KeyRejected::invalid_encoding())?;
+        Ok(Self {
+            algorithm,
+            evp_pkey,
+        })
+    }
+
+    /// Encapsulates a fresh shared secret, returning the ciphertext to be sent to the
+    /// holder of the decapsulation key along with the shared secret.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn encapsulate(&self) -> Result<(Ciphertext, SharedSecret), Unspecified> {
+        let ctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new(*self.evp_pkey, null_mut()) })?;
+        let mut ciphertext = vec![0u8; self.algorithm.ciphertext_len].into_boxed_slice();
+        let mut ciphertext_len = ciphertext.len();
+        let mut shared_secret = SharedSecret(vec![0u8; self.algorithm.shared_secret_len].into());
+        let mut shared_secret_len = shared_secret.0.len();
+        if 1 != unsafe {
+            EVP_PKEY_encapsulate(
+                *ctx,
+                ciphertext.as_mut_ptr(),
+                &mut ciphertext_len,
+                shared_secret.0.as_mut_ptr(),
+                &mut shared_secret_len,
+            )
+        } || ciphertext_len != self.algorithm.ciphertext_len
+            || shared_secret_len != self.algorithm.shared_secret_len
+        {
+            return Err(Unspecified);
+        }
+        Ok((Ciphertext(ciphertext), shared_secret))
+    }
+
+    /// Serializes the encapsulation key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn key_bytes(&self) -> Result<Box<[u8]>, Unspecified> {
+        raw_public_key(self.algorithm, &self.evp_pkey)
+    }
+
+    /// The algorithm for the key.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+}
+
+impl Debug for EncapsulationKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "EncapsulationKey {{ algorithm: {:?} }}",
+            self.algorithm
+        ))
+    }
+}
+
+fn raw_public_key(
+    algorithm: &'static Algorithm,
+    evp_pkey: &LcPtr<*mut EVP_PKEY>,
+) -> Result<Box<[u8]>, Unspecified> {
+    let mut key_bytes = vec![0u8; algorithm.encapsulation_key_len].into_boxed_slice();
+    let mut key_len = key_bytes.len();
+    if 1 != unsafe { EVP_PKEY_get_raw_public_key(**evp_pkey, key_bytes.as_mut_ptr(), &mut key_len) }
+        || key_len != algorithm.encapsulation_key_len
+    {
+        return Err(Unspecified);
+    }
+    Ok(key_bytes)
+}
+
+/// A ciphertext produced by encapsulation.
+#[derive(Clone)]
+pub struct Ciphertext(Box<[u8]>);
+
+impl AsRef<[u8]> for Ciphertext {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl Debug for Ciphertext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "Ciphertext(\"{}\")",
+            test::to_hex(self.0.as_ref())
+        ))
+    }
+}
+
+/// A shared secret produced by encapsulation. It is zeroized when dropped.
+pub struct SharedSecret(Box<[u8]>);
+
+impl SharedSecret {
+    #[inline]
+    pub(crate) fn new(bytes: Box<[u8]>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for SharedSecret {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Debug for SharedSecret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("SharedSecret()")
+    }
+}
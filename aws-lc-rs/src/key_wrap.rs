@@ -0,0 +1,262 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! AES key wrapping, as specified in [RFC 3394] (KW) and [RFC 5649] (KWP, key wrap with
+//! padding). Both are also specified in [NIST SP 800-38F].
+//!
+//! KW wraps keys whose length is a multiple of 8 bytes and at least 16 bytes. KWP wraps
+//! keys of any non-zero length.
+//!
+//! [RFC 3394]: https://tools.ietf.org/html/rfc3394
+//! [RFC 5649]: https://tools.ietf.org/html/rfc5649
+//! [NIST SP 800-38F]: https://csrc.nist.gov/publications/detail/sp/800-38f/final
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::key_wrap::{KeyEncryptionKey, AES_256, KEY_WRAP_OVERHEAD};
+//!
+//! let kek = KeyEncryptionKey::new(&AES_256, &[0x42; 32])?;
+//! let data_key = [0x17u8; 32];
+//!
+//! let mut wrapped = [0u8; 32 + KEY_WRAP_OVERHEAD];
+//! let wrapped = kek.wrap(&data_key, &mut wrapped)?;
+//!
+//! let mut unwrapped = [0u8; 32];
+//! let unwrapped = kek.unwrap(wrapped, &mut unwrapped)?;
+//! assert_eq!(&data_key, unwrapped);
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+
+use crate::cipher::key::SymmetricCipherKey;
+use crate::cipher::{AES_128_KEY_LEN, AES_256_KEY_LEN};
+use crate::error::Unspecified;
+use aws_lc::{AES_unwrap_key, AES_unwrap_key_padded, AES_wrap_key, AES_wrap_key_padded, AES_KEY};
+use std::fmt::Debug;
+use std::ptr::null;
+
+/// The number of bytes that wrapping adds to a key: a wrapped key is exactly this much
+/// longer than the key with KW, and at most this much longer than the key padded to a
+/// multiple of 8 bytes with KWP.
+pub const KEY_WRAP_OVERHEAD: usize = 8;
+
+const SEMIBLOCK_LEN: usize = 8;
+
+/// Key wrap algorithm identifier.
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AlgorithmId {
+    /// AES with a 128-bit key encryption key.
+    Aes128,
+
+    /// AES with a 256-bit key encryption key.
+    Aes256,
+}
+
+/// A key wrap algorithm.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Algorithm {
+    id: AlgorithmId,
+    key_len: usize,
+}
+
+/// AES key wrap with a 128-bit key encryption key.
+pub static AES_128: Algorithm = Algorithm {
+    id: AlgorithmId::Aes128,
+    key_len: AES_128_KEY_LEN,
+};
+
+/// AES key wrap with a 256-bit key encryption key.
+pub static AES_256: Algorithm = Algorithm {
+    id: AlgorithmId::Aes256,
+    key_len: AES_256_KEY_LEN,
+};
+
+impl Algorithm {
+    /// Returns the algorithm identifier.
+    #[must_use]
+    pub fn id(&self) -> AlgorithmId {
+        self.id
+    }
+
+    /// Returns the length in bytes of the key encryption key.
+    #[must_use]
+    pub fn key_len(&self) -> usize {
+        self.key_len
+    }
+}
+
+/// A key encryption key (KEK), used to wrap and unwrap other keys.
+pub struct KeyEncryptionKey {
+    algorithm: &'static Algorithm,
+    key: SymmetricCipherKey,
+}
+
+impl Debug for KeyEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyEncryptionKey")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyEncryptionKey {
+    /// Constructs a new key encryption key for `algorithm`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `key_bytes.len()` does not match the length required by
+    /// `algorithm`.
+    pub fn new(algorithm: &'static Algorithm, key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        let key = match algorithm.id {
+            AlgorithmId::Aes128 => SymmetricCipherKey::aes128(key_bytes),
+            AlgorithmId::Aes256 => SymmetricCipherKey::aes256(key_bytes),
+        }?;
+        Ok(Self { algorithm, key })
+    }
+
+    /// Returns the key wrap algorithm.
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+
+    /// Wraps `input` using KW (RFC 3394) with the default initial value, writing the result
+    /// to `output`. Returns the written part of `output`, which is
+    /// `input.len() + KEY_WRAP_OVERHEAD` bytes long.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `input` is shorter than 16 bytes or its length is not a
+    /// multiple of 8, or if `output` is too small.
+    pub fn wrap<'output>(
+        &self,
+        input: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified> {
+        if input.len() < 2 * SEMIBLOCK_LEN
+            || input.len() % SEMIBLOCK_LEN != 0
+            || output.len() < input.len() + KEY_WRAP_OVERHEAD
+        {
+            return Err(Unspecified);
+        }
+        let written = unsafe {
+            AES_wrap_key(
+                self.enc_key(),
+                null(),
+                output.as_mut_ptr(),
+                input.as_ptr(),
+                input.len(),
+            )
+        };
+        let written = usize::try_from(written).map_err(|_| Unspecified)?;
+        Ok(&mut output[..written])
+    }
+
+    /// Unwraps `input`, which was wrapped using KW (RFC 3394) with the default initial
+    /// value, writing the key to `output`. Returns the written part of `output`, which is
+    /// `input.len() - KEY_WRAP_OVERHEAD` bytes long.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `input` is not a valid wrapped key for this key encryption
+    /// key, or if `output` is too small.
+    pub fn unwrap<'output>(
+        &self,
+        input: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified> {
+        if input.len() < 3 * SEMIBLOCK_LEN
+            || input.len() % SEMIBLOCK_LEN != 0
+            || output.len() < input.len() - KEY_WRAP_OVERHEAD
+        {
+            return Err(Unspecified);
+        }
+        let written = unsafe {
+            AES_unwrap_key(
+                self.dec_key(),
+                null(),
+                output.as_mut_ptr(),
+                input.as_ptr(),
+                input.len(),
+            )
+        };
+        let written = usize::try_from(written).map_err(|_| Unspecified)?;
+        Ok(&mut output[..written])
+    }
+
+    /// Wraps `input` using KWP (RFC 5649), writing the result to `output`. Returns the
+    /// written part of `output`, which is `input.len()` rounded up to a multiple of 8,
+    /// plus `KEY_WRAP_OVERHEAD`, bytes long.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `input` is empty, or if `output` is too small.
+    pub fn wrap_with_padding<'output>(
+        &self,
+        input: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified> {
+        let mut written = 0;
+        if 1 != unsafe {
+            AES_wrap_key_padded(
+                self.enc_key(),
+                output.as_mut_ptr(),
+                &mut written,
+                output.len(),
+                input.as_ptr(),
+                input.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(&mut output[..written])
+    }
+
+    /// Unwraps `input`, which was wrapped using KWP (RFC 5649), writing the key to
+    /// `output`. Returns the written part of `output`.
+    ///
+    /// `output` must be at least `input.len() - KEY_WRAP_OVERHEAD` bytes long; the
+    /// unwrapped key may be up to 7 bytes shorter than that.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `input` is not a valid wrapped key for this key encryption
+    /// key, or if `output` is too small.
+    pub fn unwrap_with_padding<'output>(
+        &self,
+        input: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified> {
+        let mut written = 0;
+        if 1 != unsafe {
+            AES_unwrap_key_padded(
+                self.dec_key(),
+                output.as_mut_ptr(),
+                &mut written,
+                output.len(),
+                input.as_ptr(),
+                input.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(&mut output[..written])
+    }
+
+    fn enc_key(&self) -> &AES_KEY {
+        match &self.key {
+            SymmetricCipherKey::Aes128 { enc_key, .. }
+            | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
+            SymmetricCipherKey::ChaCha20 { .. } | SymmetricCipherKey::Aes256Xts { .. } => {
+                unreachable!()
+            }
+        }
+    }
+
+    fn dec_key(&self) -> &AES_KEY {
+        match &self.key {
+            SymmetricCipherKey::Aes128 { dec_key, .. }
+            | SymmetricCipherKey::Aes256 { dec_key, .. } => dec_key,
+            SymmetricCipherKey::ChaCha20 { .. } | SymmetricCipherKey::Aes256Xts { .. } => {
+                unreachable!()
+            }
+        }
+    }
+}
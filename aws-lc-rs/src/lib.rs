@@ -45,6 +45,10 @@
 //! Enable feature to preserve compatibility with ring's `signature::VerificationAlgorithm::verify`
 //! function. This adds a requirement on `untrusted = "0.7.1"`.
 //!
+//! #### - pem ####
+//! Enable feature to construct keys from, and serialize keys to, the PEM format. See the `pem`
+//! module.
+//!
 //! #### - fips ####
 //! **EXPERIMENTAL** Enable this feature to have aws-lc-rs use the
 //! [*aws-lc-fips-sys*](https://crates.io/crates/aws-lc-fips-sys) crate for the cryptographic
@@ -104,15 +108,23 @@ pub mod aead;
 pub mod agreement;
 pub mod constant_time;
 pub mod digest;
+pub mod encryption;
 pub mod error;
 pub mod hkdf;
 pub mod hmac;
+pub mod hpke;
+pub mod hybrid;
 #[cfg(feature = "ring-io")]
 pub mod io;
+pub mod kem;
+pub mod key_wrap;
 pub mod pbkdf2;
+#[cfg(feature = "pem")]
+pub mod pem;
 pub mod pkcs8;
 pub mod rand;
 pub mod signature;
+pub mod spki;
 pub mod test;
 
 mod bn;
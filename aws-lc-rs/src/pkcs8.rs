@@ -5,21 +5,71 @@
 
 //! PKCS#8 is specified in [RFC 5208].
 //!
+//! Password-protected documents, in the `EncryptedPrivateKeyInfo` format of [RFC 5958], are
+//! produced by [`encrypt`] and read by [`decrypt`]. [`encrypt`] uses PBES2 ([RFC 8018]) with
+//! PBKDF2-HMAC-SHA256 and AES-256-CBC, which is also what OpenSSL produces by default.
+//! [`decrypt`] accepts PBES2 documents with any of the PBKDF2 PRFs and ciphers that AWS-LC
+//! supports. The decrypted document can be passed to `RsaKeyPair::from_pkcs8`,
+//! `EcdsaKeyPair::from_pkcs8` or `Ed25519KeyPair::from_pkcs8`.
+//!
 //! [RFC 5208]: https://tools.ietf.org/html/rfc5208.
+//! [RFC 5958]: https://tools.ietf.org/html/rfc5958
+//! [RFC 8018]: https://tools.ietf.org/html/rfc8018
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::signature::{self, EcdsaKeyPair, KeyPair};
+//! use aws_lc_rs::{pkcs8, rand};
+//! use std::num::NonZeroU32;
+//!
+//! let rng = rand::SystemRandom::new();
+//! let alg = &signature::ECDSA_P256_SHA256_ASN1_SIGNING;
+//! let document = EcdsaKeyPair::generate_pkcs8(alg, &rng)?;
+//!
+//! let iterations = NonZeroU32::new(100_000).unwrap();
+//! let encrypted = pkcs8::encrypt(&rng, document.as_ref(), b"password", iterations)?;
+//!
+//! let decrypted = pkcs8::decrypt(encrypted.as_ref(), b"password")?;
+//! let key_pair = EcdsaKeyPair::from_pkcs8(alg, decrypted.as_ref())?;
+//!
+//! assert!(pkcs8::decrypt(encrypted.as_ref(), b"wrong password").is_err());
+//!
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
 
-use crate::ec;
-use zeroize::Zeroize;
+use crate::cipher::{
+    EncryptionContext, PaddedBlockEncryptingKey, UnboundCipherKey, AES_256, AES_256_KEY_LEN,
+};
+use crate::error::{KeyRejected, Unspecified};
+use crate::iv::{FixedLength, IV_LEN_128_BIT};
+use crate::ptr::LcPtr;
+use crate::rand::SecureRandom;
+use crate::{cbb, cbs, pbkdf2};
+use aws_lc::{
+    d2i_X509_SIG, i2d_PKCS8_PRIV_KEY_INFO, CBB_add_asn1, CBB_add_asn1_uint64, CBB_add_bytes,
+    CBB_flush, CBS_data, CBS_get_asn1, CBS_get_asn1_uint64, CBS_len, PKCS8_decrypt, CBB, CBS,
+    CBS_ASN1_NULL, CBS_ASN1_OBJECT, CBS_ASN1_OCTETSTRING, CBS_ASN1_SEQUENCE, CBS_ASN1_TAG,
+    EVP_PKEY,
+};
+use std::mem::MaybeUninit;
+use std::num::NonZeroU32;
+use std::os::raw::{c_int, c_long};
+use std::ptr::null_mut;
+use zeroize::{Zeroize, Zeroizing};
 
 /// A generated PKCS#8 document.
+///
+/// The document is heap-allocated, so it can hold keys of any size, including
+/// large RSA keys.
 pub struct Document {
-    pub(crate) bytes: [u8; ec::PKCS8_DOCUMENT_MAX_LEN],
-    pub(crate) len: usize,
+    pub(crate) bytes: Box<[u8]>,
 }
 
 impl AsRef<[u8]> for Document {
     #[inline]
     fn as_ref(&self) -> &[u8] {
-        &self.bytes[..self.len]
+        &self.bytes
     }
 }
 
@@ -34,3 +84,259 @@ pub(crate) enum Version {
     V1,
     V2,
 }
+
+const SALT_LEN: usize = 16;
+
+/// The largest PBKDF2 iteration count that [`decrypt`] accepts and that [`encrypt`] uses.
+///
+/// The iteration count of an encrypted document is chosen by whoever produced it, so without
+/// a limit an untrusted document could make [`decrypt`] spend minutes deriving the key.
+pub const MAX_ITERATIONS: u32 = 10_000_000;
+
+// The contents of the DER-encoded object identifiers.
+// 1.2.840.113549.1.5.13
+const PBES2_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x05, 0x0d];
+// 1.2.840.113549.1.5.12
+const PBKDF2_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x05, 0x0c];
+// 1.2.840.113549.2.9
+const HMAC_WITH_SHA256_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x09];
+// 2.16.840.1.101.3.4.1.42
+const AES_256_CBC_OID: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x2a];
+
+/// Encrypts the PKCS#8 document `pkcs8` with `password`, returning a DER-encoded
+/// `EncryptedPrivateKeyInfo`.
+///
+/// The encryption key is derived from `password` using PBKDF2-HMAC-SHA256 with
+/// `iterations` rounds and a random salt, and the document is encrypted with
+/// AES-256-CBC under a random IV.
+///
+/// # Errors
+/// `error::Unspecified` if `pkcs8` is not a valid PKCS#8 document, if `iterations` is
+/// greater than [`MAX_ITERATIONS`], if `rng` cannot provide enough bits, or if there's an
+/// internal error.
+pub fn encrypt(
+    rng: &dyn SecureRandom,
+    pkcs8: &[u8],
+    password: &[u8],
+    iterations: NonZeroU32,
+) -> Result<Document, Unspecified> {
+    if iterations.get() > MAX_ITERATIONS {
+        return Err(Unspecified);
+    }
+    LcPtr::<*mut EVP_PKEY>::try_from(pkcs8)?;
+
+    let mut salt = [0u8; SALT_LEN];
+    rng.fill(&mut salt)?;
+    let mut iv = [0u8; IV_LEN_128_BIT];
+    rng.fill(&mut iv)?;
+
+    let key = derive_key(iterations, &salt, password)?;
+    let key = PaddedBlockEncryptingKey::cbc_pkcs7(key)?;
+    let mut ciphertext = Zeroizing::new(pkcs8.to_vec());
+    key.less_safe_encrypt(
+        &mut *ciphertext,
+        EncryptionContext::Iv128(FixedLength::from(iv)),
+    )?;
+
+    let bytes = unsafe { marshal_encrypted_private_key_info(&salt, iterations, &iv, &ciphertext)? };
+    Ok(Document { bytes })
+}
+
+/// Decrypts the DER-encoded `EncryptedPrivateKeyInfo` `encrypted` with `password`,
+/// returning the PKCS#8 document it contains.
+///
+/// # Errors
+/// `error::KeyRejected` with `WrongAlgorithm` if `encrypted` uses a scheme other than PBES2
+/// with PBKDF2, with `TooLarge` if its PBKDF2 iteration count is greater than
+/// [`MAX_ITERATIONS`], or with `InvalidEncoding` if `encrypted` is malformed, uses a PRF or
+/// cipher that is not supported, `password` is wrong, or the decrypted document is not
+/// valid PKCS#8.
+pub fn decrypt(encrypted: &[u8], password: &[u8]) -> Result<Document, KeyRejected> {
+    let iterations = unsafe { parse_pbkdf2_iterations(encrypted)? };
+    if iterations > u64::from(MAX_ITERATIONS) {
+        return Err(KeyRejected::too_large());
+    }
+
+    let encrypted_len = c_long::try_from(encrypted.len())?;
+    let password_len = c_int::try_from(password.len())?;
+    unsafe {
+        let mut encrypted_ptr = encrypted.as_ptr();
+        let x509_sig = LcPtr::new(d2i_X509_SIG(null_mut(), &mut encrypted_ptr, encrypted_len))
+            .map_err(|()| KeyRejected::invalid_encoding())?;
+
+        let p8_info = LcPtr::new(PKCS8_decrypt(
+            *x509_sig,
+            password.as_ptr().cast(),
+            password_len,
+        ))
+        .map_err(|()| KeyRejected::invalid_encoding())?;
+
+        let mut der = null_mut::<u8>();
+        let der_len = i2d_PKCS8_PRIV_KEY_INFO(*p8_info, &mut der);
+        let der = LcPtr::new(der).map_err(|()| KeyRejected::unexpected_error())?;
+        let der_len = usize::try_from(der_len).map_err(|_| KeyRejected::unexpected_error())?;
+        let plaintext = std::slice::from_raw_parts(*der, der_len);
+
+        // A wrong password almost always makes `PKCS8_decrypt` fail on the padding; in
+        // the rare case that it doesn't, the plaintext will not parse.
+        LcPtr::<*mut EVP_PKEY>::try_from(plaintext)?;
+
+        Ok(Document {
+            bytes: Box::from(plaintext),
+        })
+    }
+}
+
+fn derive_key(
+    iterations: NonZeroU32,
+    salt: &[u8],
+    password: &[u8],
+) -> Result<UnboundCipherKey, Unspecified> {
+    let mut key_bytes = Zeroizing::new([0u8; AES_256_KEY_LEN]);
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        password,
+        key_bytes.as_mut(),
+    );
+    UnboundCipherKey::new(&AES_256, key_bytes.as_ref())
+}
+
+// EncryptedPrivateKeyInfo ::= SEQUENCE {
+//   encryptionAlgorithm SEQUENCE {
+//     algorithm   pbes2,
+//     parameters  SEQUENCE {
+//       keyDerivationFunc SEQUENCE {
+//         algorithm   pbkdf2,
+//         parameters  SEQUENCE {
+//           salt            OCTET STRING,
+//           iterationCount  INTEGER,
+//           keyLength       INTEGER OPTIONAL,
+//           prf             SEQUENCE { hmacWithSHA256, NULL } } },
+//       encryptionScheme SEQUENCE {
+//         algorithm   aes256-CBC,
+//         parameters  OCTET STRING (the IV) } } },
+//   encryptedData OCTET STRING }
+//
+// `PKCS8_encrypt` isn't used here because, for PBES2, it always omits the PRF, so the key is
+// derived with the default hmacWithSHA1 instead of hmacWithSHA256.
+unsafe fn marshal_encrypted_private_key_info(
+    salt: &[u8],
+    iterations: NonZeroU32,
+    iv: &[u8],
+    ciphertext: &[u8],
+) -> Result<Box<[u8]>, Unspecified> {
+    let mut cbb = cbb::build_CBB(ciphertext.len() + 128);
+    add_asn1(cbb.as_mut_ptr(), CBS_ASN1_SEQUENCE, |info| {
+        add_asn1(info, CBS_ASN1_SEQUENCE, |algorithm| {
+            add_asn1_bytes(algorithm, CBS_ASN1_OBJECT, PBES2_OID)?;
+            add_asn1(algorithm, CBS_ASN1_SEQUENCE, |params| {
+                add_asn1(params, CBS_ASN1_SEQUENCE, |kdf| {
+                    add_asn1_bytes(kdf, CBS_ASN1_OBJECT, PBKDF2_OID)?;
+                    add_asn1(kdf, CBS_ASN1_SEQUENCE, |kdf_params| {
+                        add_asn1_bytes(kdf_params, CBS_ASN1_OCTETSTRING, salt)?;
+                        if 1 != CBB_add_asn1_uint64(kdf_params, u64::from(iterations.get())) {
+                            return Err(Unspecified);
+                        }
+                        add_asn1(kdf_params, CBS_ASN1_SEQUENCE, |prf| {
+                            add_asn1_bytes(prf, CBS_ASN1_OBJECT, HMAC_WITH_SHA256_OID)?;
+                            add_asn1_bytes(prf, CBS_ASN1_NULL, &[])
+                        })
+                    })
+                })?;
+                add_asn1(params, CBS_ASN1_SEQUENCE, |scheme| {
+                    add_asn1_bytes(scheme, CBS_ASN1_OBJECT, AES_256_CBC_OID)?;
+                    add_asn1_bytes(scheme, CBS_ASN1_OCTETSTRING, iv)
+                })
+            })
+        })?;
+        add_asn1_bytes(info, CBS_ASN1_OCTETSTRING, ciphertext)
+    })?;
+    cbb.finish()
+}
+
+unsafe fn add_asn1<F>(cbb: *mut CBB, tag: i32, contents: F) -> Result<(), Unspecified>
+where
+    F: FnOnce(*mut CBB) -> Result<(), Unspecified>,
+{
+    #[allow(clippy::cast_sign_loss)]
+    let tag = tag as CBS_ASN1_TAG;
+    let mut child = MaybeUninit::<CBB>::uninit();
+    if 1 != CBB_add_asn1(cbb, child.as_mut_ptr(), tag) {
+        return Err(Unspecified);
+    }
+    contents(child.as_mut_ptr())?;
+    if 1 != CBB_flush(cbb) {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+unsafe fn add_asn1_bytes(cbb: *mut CBB, tag: i32, bytes: &[u8]) -> Result<(), Unspecified> {
+    add_asn1(cbb, tag, |contents| {
+        if 1 != CBB_add_bytes(contents, bytes.as_ptr(), bytes.len()) {
+            return Err(Unspecified);
+        }
+        Ok(())
+    })
+}
+
+// Returns the PBKDF2 iteration count of a PBES2 `EncryptedPrivateKeyInfo`, so that it can be
+// checked before `PKCS8_decrypt` derives the key. `PKCS8_decrypt` parses the rest of the
+// document.
+unsafe fn parse_pbkdf2_iterations(input: &[u8]) -> Result<u64, KeyRejected> {
+    let mut input = cbs::build_CBS(input);
+    let mut info = get_asn1(&mut input, CBS_ASN1_SEQUENCE)?;
+    expect_empty(&input)?;
+
+    let mut algorithm = get_asn1(&mut info, CBS_ASN1_SEQUENCE)?;
+    expect_oid(&mut algorithm, PBES2_OID)?;
+    let mut params = get_asn1(&mut algorithm, CBS_ASN1_SEQUENCE)?;
+
+    let mut kdf = get_asn1(&mut params, CBS_ASN1_SEQUENCE)?;
+    expect_oid(&mut kdf, PBKDF2_OID)?;
+    let mut kdf_params = get_asn1(&mut kdf, CBS_ASN1_SEQUENCE)?;
+
+    // salt
+    get_asn1(&mut kdf_params, CBS_ASN1_OCTETSTRING)?;
+    let mut iterations = 0u64;
+    if 1 != CBS_get_asn1_uint64(&mut kdf_params, &mut iterations) {
+        return Err(KeyRejected::invalid_encoding());
+    }
+    Ok(iterations)
+}
+
+unsafe fn get_asn1(cbs: &mut CBS, tag: i32) -> Result<CBS, KeyRejected> {
+    #[allow(clippy::cast_sign_loss)]
+    let tag = tag as CBS_ASN1_TAG;
+    let mut out = MaybeUninit::<CBS>::uninit();
+    if 1 != CBS_get_asn1(cbs, out.as_mut_ptr(), tag) {
+        return Err(KeyRejected::invalid_encoding());
+    }
+    Ok(out.assume_init())
+}
+
+unsafe fn expect_oid(cbs: &mut CBS, oid: &[u8]) -> Result<(), KeyRejected> {
+    let actual = get_asn1(cbs, CBS_ASN1_OBJECT)?;
+    if oid != cbs_bytes(&actual) {
+        return Err(KeyRejected::wrong_algorithm());
+    }
+    Ok(())
+}
+
+unsafe fn expect_empty(cbs: &CBS) -> Result<(), KeyRejected> {
+    if 0 != CBS_len(cbs) {
+        return Err(KeyRejected::invalid_encoding());
+    }
+    Ok(())
+}
+
+// The returned slice borrows from the buffer that `cbs` was built over.
+unsafe fn cbs_bytes<'a>(cbs: &CBS) -> &'a [u8] {
+    let len = CBS_len(cbs);
+    if len == 0 {
+        return &[];
+    }
+    std::slice::from_raw_parts(CBS_data(cbs), len)
+}
@@ -12,26 +12,30 @@ use crate::digest::match_digest_type;
 use crate::error::{KeyRejected, Unspecified};
 #[cfg(feature = "ring-io")]
 use crate::io;
+#[cfg(feature = "pem")]
+use crate::pem;
+use crate::pkcs8::{Document, Version};
 use crate::ptr::{ConstPointer, DetachableLcPtr, LcPtr};
 use crate::sealed::Sealed;
 use crate::signature::{KeyPair, VerificationAlgorithm};
-use crate::{cbs, digest, rand, test};
-#[cfg(feature = "fips")]
-use aws_lc::RSA_check_fips;
-#[cfg(not(feature = "fips"))]
-use aws_lc::RSA_check_key;
+use crate::{cbs, digest, rand, spki, test};
 use aws_lc::{
-    RSA_bits, RSA_get0_e, RSA_get0_n, RSA_get0_p, RSA_get0_q, RSA_new, RSA_parse_private_key,
-    RSA_parse_public_key, RSA_public_key_to_bytes, RSA_set0_key, RSA_sign, RSA_sign_pss_mgf1,
-    RSA_size, RSA_verify, RSA_verify_pss_mgf1, RSA,
+    EVP_PKEY_new, EVP_PKEY_set1_RSA, RSA_bits, RSA_get0_e, RSA_get0_n, RSA_get0_p, RSA_get0_q,
+    RSA_new, RSA_parse_private_key, RSA_parse_public_key, RSA_private_key_to_bytes,
+    RSA_public_key_to_bytes, RSA_set0_key, RSA_sign, RSA_sign_pss_mgf1, RSA_size, RSA_verify,
+    RSA_verify_pss_mgf1, EVP_PKEY, RSA,
 };
+#[cfg(feature = "fips")]
+use aws_lc::{RSA_check_fips, RSA_generate_key_fips};
+#[cfg(not(feature = "fips"))]
+use aws_lc::{RSA_check_key, RSA_generate_key_ex};
 use core::fmt;
 
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
 use std::mem::MaybeUninit;
 use std::ops::RangeInclusive;
-use std::os::raw::c_uint;
+use std::os::raw::{c_int, c_uint};
 use std::ptr::{null, null_mut};
 use std::slice;
 
@@ -71,7 +75,7 @@ impl RsaKeyPair {
     ///
     /// Only two-prime (not multi-prime) keys are supported. The public modulus
     /// (n) must be at least 2047 bits. The public modulus must be no larger
-    /// than 4096 bits. It is recommended that the public modulus be exactly
+    /// than 8192 bits. It is recommended that the public modulus be exactly
     /// 2048 or 3072 bits. The public exponent must be at least 65537.
     ///
     /// The following will generate a 2048-bit RSA private key of the correct form using
@@ -135,10 +139,109 @@ impl RsaKeyPair {
             Self::new(rsa)
         }
     }
+
+    /// Generates a new RSA key pair with a public modulus of `bits` bits and a public
+    /// exponent of 65537.
+    ///
+    /// `bits` must be 2048, 3072, 4096 or 8192. Generating large keys can take a long
+    /// time, especially for 8192-bit keys.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `bits` is not supported, or on internal error. With the
+    /// "fips" feature enabled, 8192-bit keys are not supported.
+    pub fn generate(bits: usize) -> Result<Self, Unspecified> {
+        let rsa = unsafe { generate_rsa_key(bits)? };
+        Ok(Self::new(rsa)?)
+    }
+
+    /// Generates a new RSA key pair as described for `generate`, and returns it
+    /// serialized as a PKCS#8 v1 document.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `bits` is not supported, or on internal error.
+    pub fn generate_pkcs8(bits: usize) -> Result<Document, Unspecified> {
+        unsafe {
+            let rsa = generate_rsa_key(bits)?;
+            let evp_pkey = LcPtr::new(EVP_PKEY_new())?;
+            if 1 != EVP_PKEY_set1_RSA(*evp_pkey, *rsa) {
+                return Err(Unspecified);
+            }
+            evp_pkey.marshall_private_key(Version::V1)
+        }
+    }
+
+    /// Serializes this key pair as a PKCS#8 v1 `PrivateKeyInfo` document.
+    ///
+    /// There is no `to_pkcs8v2` for RSA keys: AWS-LC's `EVP_marshal_private_key_v2` can
+    /// only encode Ed25519 keys. The v1 document already includes the public key, inside
+    /// the `RSAPrivateKey`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn to_pkcs8v1(&self) -> Result<Document, Unspecified> {
+        unsafe {
+            let evp_pkey = LcPtr::new(EVP_PKEY_new())?;
+            if 1 != EVP_PKEY_set1_RSA(*evp_pkey, *self.rsa_key) {
+                return Err(Unspecified);
+            }
+            evp_pkey.marshall_private_key(Version::V1)
+        }
+    }
+
+    /// Serializes this key pair as a DER-encoded `RSAPrivateKey` structure (RFC 8017),
+    /// the format accepted by `RsaKeyPair::from_der`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn to_der(&self) -> Result<RsaPrivateKeyDer, Unspecified> {
+        unsafe {
+            let mut der_bytes = MaybeUninit::<*mut u8>::uninit();
+            let mut outlen = MaybeUninit::<usize>::uninit();
+            if 1 != RSA_private_key_to_bytes(
+                der_bytes.as_mut_ptr(),
+                outlen.as_mut_ptr(),
+                *self.rsa_key,
+            ) {
+                return Err(Unspecified);
+            }
+            let der_bytes = LcPtr::new(der_bytes.assume_init())?;
+            let der_slice = slice::from_raw_parts(*der_bytes, outlen.assume_init());
+            Ok(RsaPrivateKeyDer(Vec::from(der_slice).into_boxed_slice()))
+        }
+    }
+
+    /// Parses a PEM-encoded RSA private key: either an unencrypted PKCS#8 document
+    /// (`PRIVATE KEY`) or an `RSAPrivateKey` structure (`RSA PRIVATE KEY`).
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the PEM label is for another kind of key, or as described
+    /// for `from_pkcs8` and `from_der`.
+    #[cfg(feature = "pem")]
+    pub fn from_pem(pem: &str) -> Result<Self, KeyRejected> {
+        let (label, der) = pem::decode(pem)?;
+        match label {
+            pem::Label::PrivateKey => Self::from_pkcs8(&der),
+            pem::Label::RsaPrivateKey => Self::from_der(&der),
+            pem::Label::EcPrivateKey => Err(KeyRejected::wrong_algorithm()),
+            pem::Label::PublicKey | pem::Label::RsaPublicKey => {
+                Err(KeyRejected::unexpected_pem_label())
+            }
+        }
+    }
+
+    /// Serializes this key pair as a PEM-encoded PKCS#8 v1 document (`PRIVATE KEY`).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "pem")]
+    pub fn to_pem(&self) -> Result<pem::Document, Unspecified> {
+        pem::encode(pem::Label::PrivateKey, self.to_pkcs8v1()?.as_ref())
+    }
+
     const MIN_RSA_BITS: u32 = 1024;
-    const MAX_RSA_BITS: u32 = 2048;
+    const MAX_RSA_BITS: u32 = 4096;
 
-    unsafe fn validate_rsa(rsa: &ConstPointer<RSA>) -> Result<(), KeyRejected> {
+    pub(crate) unsafe fn validate_rsa(rsa: &ConstPointer<RSA>) -> Result<(), KeyRejected> {
         let p = ConstPointer::new(RSA_get0_p(**rsa))?;
         let q = ConstPointer::new(RSA_get0_q(**rsa))?;
         let p_bits = p.num_bits();
@@ -198,10 +301,19 @@ impl VerificationAlgorithm for RsaParameters {
         public_key: &[u8],
         msg: &[u8],
         signature: &[u8],
+    ) -> Result<(), Unspecified> {
+        self.verify_digest_sig(public_key, &digest::digest(self.0, msg), signature)
+    }
+
+    fn verify_digest_sig(
+        &self,
+        public_key: &[u8],
+        digest: &digest::Digest,
+        signature: &[u8],
     ) -> Result<(), Unspecified> {
         unsafe {
             let rsa = build_public_RSA(public_key)?;
-            verify_RSA(self.0, self.1, &rsa, msg, signature, &self.2)
+            verify_RSA(self.0, self.1, &rsa, digest, signature, &self.2)
         }
     }
 }
@@ -216,7 +328,8 @@ impl RsaKeyPair {
     /// Many other crypto libraries have signing functions that takes a
     /// precomputed digest as input, instead of the message to digest. This
     /// function does *not* take a precomputed digest; instead, `sign`
-    /// calculates the digest itself.
+    /// calculates the digest itself. See `sign_digest` for signing a
+    /// precomputed digest.
     ///
     /// # *ring* Compatibility
     /// Our implementation ignores the `SecureRandom` parameter.
@@ -227,18 +340,45 @@ impl RsaKeyPair {
     pub fn sign(
         &self,
         padding_alg: &'static dyn RsaEncoding,
-        _rng: &dyn rand::SecureRandom,
+        rng: &dyn rand::SecureRandom,
         msg: &[u8],
         signature: &mut [u8],
+    ) -> Result<(), Unspecified> {
+        let digest = digest::digest(padding_alg.encoding().0, msg);
+        self.sign_digest(padding_alg, rng, &digest, signature)
+    }
+
+    /// Sign the message digest `digest`, which is padded using the padding
+    /// algorithm from `padding_alg`. The signature it written into `signature`;
+    /// `signature`'s length must be exactly the length returned by
+    /// `public_modulus_len()`.
+    ///
+    /// This is useful when the message is too large to hold in memory and is
+    /// digested incrementally with a `digest::Context`.
+    ///
+    /// # *ring* Compatibility
+    /// Our implementation ignores the `SecureRandom` parameter.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on error, or if the algorithm of `digest` is not the
+    /// digest algorithm of `padding_alg`.
+    pub fn sign_digest(
+        &self,
+        padding_alg: &'static dyn RsaEncoding,
+        _rng: &dyn rand::SecureRandom,
+        digest: &digest::Digest,
+        signature: &mut [u8],
     ) -> Result<(), Unspecified> {
         let encoding = padding_alg.encoding();
         let mut output_len = self.public_modulus_len();
         if signature.len() != output_len {
             return Err(Unspecified);
         }
+        let digest_alg = encoding.0;
+        if digest.algorithm() != digest_alg {
+            return Err(Unspecified);
+        }
         unsafe {
-            let digest_alg = encoding.0;
-            let digest = digest::digest(digest_alg, msg);
             let digest = digest.as_ref();
 
             let padding = encoding.1;
@@ -302,6 +442,52 @@ impl Debug for RsaKeyPair {
     }
 }
 
+unsafe fn generate_rsa_key(bits: usize) -> Result<LcPtr<*mut RSA>, Unspecified> {
+    const SUPPORTED_BITS: [usize; 4] = [2048, 3072, 4096, 8192];
+    if !SUPPORTED_BITS.contains(&bits) {
+        return Err(Unspecified);
+    }
+    let bits = c_int::try_from(bits)?;
+    let rsa = LcPtr::new(RSA_new())?;
+
+    #[cfg(not(feature = "fips"))]
+    {
+        let e = DetachableLcPtr::try_from(65537)?;
+        if 1 != RSA_generate_key_ex(*rsa, bits, *e, null_mut()) {
+            return Err(Unspecified);
+        }
+    }
+
+    #[cfg(feature = "fips")]
+    if 1 != RSA_generate_key_fips(*rsa, bits, null_mut()) {
+        return Err(Unspecified);
+    }
+
+    Ok(rsa)
+}
+
+/// A DER-encoded `RSAPrivateKey` structure. It is zeroized when dropped.
+pub struct RsaPrivateKeyDer(Box<[u8]>);
+
+impl AsRef<[u8]> for RsaPrivateKeyDer {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl Drop for RsaPrivateKeyDer {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Debug for RsaPrivateKeyDer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("RsaPrivateKeyDer()")
+    }
+}
+
 #[allow(non_snake_case)]
 unsafe fn serialize_RSA_pubkey(pubkey: &ConstPointer<RSA>) -> Result<Box<[u8]>, ()> {
     let mut pubkey_bytes = MaybeUninit::<*mut u8>::uninit();
@@ -366,6 +552,66 @@ impl RsaSubjectPublicKey {
     }
 }
 
+impl RsaSubjectPublicKey {
+    /// Parses a DER-encoded X.509 `SubjectPublicKeyInfo` containing an RSA public key.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `spki` is not a valid encoding, or if it does not contain
+    /// an RSA public key.
+    pub fn from_der(spki: &[u8]) -> Result<Self, KeyRejected> {
+        let evp_pkey = LcPtr::<*mut EVP_PKEY>::parse_public_key(spki)?;
+        let rsa = evp_pkey.get_rsa()?;
+        Ok(unsafe { Self::new(&rsa.as_const())? })
+    }
+
+    /// Serializes this public key as a DER-encoded X.509 `SubjectPublicKeyInfo`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn as_der(&self) -> Result<spki::Document, Unspecified> {
+        unsafe {
+            let rsa = build_public_RSA(&self.key)?;
+            let evp_pkey = LcPtr::new(EVP_PKEY_new())?;
+            if 1 != EVP_PKEY_set1_RSA(*evp_pkey, *rsa) {
+                return Err(Unspecified);
+            }
+            evp_pkey.marshall_public_key()
+        }
+    }
+
+    /// Parses a PEM-encoded RSA public key: either an X.509 `SubjectPublicKeyInfo`
+    /// (`PUBLIC KEY`) or an `RSAPublicKey` structure (`RSA PUBLIC KEY`).
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the PEM label is for another kind of key, or if the key is
+    /// not a valid RSA public key.
+    #[cfg(feature = "pem")]
+    pub fn from_pem(pem: &str) -> Result<Self, KeyRejected> {
+        let (label, der) = pem::decode(pem)?;
+        match label {
+            pem::Label::PublicKey => Self::from_der(&der),
+            pem::Label::RsaPublicKey => unsafe {
+                let rsa = build_public_RSA(&der)
+                    .map_err(|Unspecified| KeyRejected::invalid_encoding())?;
+                Ok(Self::new(&rsa.as_const())?)
+            },
+            pem::Label::PrivateKey | pem::Label::RsaPrivateKey | pem::Label::EcPrivateKey => {
+                Err(KeyRejected::unexpected_pem_label())
+            }
+        }
+    }
+
+    /// Serializes this public key as a PEM-encoded X.509 `SubjectPublicKeyInfo`
+    /// (`PUBLIC KEY`).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "pem")]
+    pub fn to_pem(&self) -> Result<pem::Document, Unspecified> {
+        pem::encode(pem::Label::PublicKey, self.as_der()?.as_ref())
+    }
+}
+
 impl Debug for RsaSubjectPublicKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
         f.write_str(&format!(
@@ -507,7 +753,7 @@ impl RsaParameters {
 
 #[inline]
 #[allow(non_snake_case)]
-unsafe fn build_public_RSA(public_key: &[u8]) -> Result<LcPtr<*mut RSA>, Unspecified> {
+pub(crate) unsafe fn build_public_RSA(public_key: &[u8]) -> Result<LcPtr<*mut RSA>, Unspecified> {
     let mut cbs = cbs::build_CBS(public_key);
 
     let rsa = LcPtr::new(RSA_parse_public_key(&mut cbs))?;
@@ -516,7 +762,7 @@ unsafe fn build_public_RSA(public_key: &[u8]) -> Result<LcPtr<*mut RSA>, Unspeci
 
 #[inline]
 #[allow(non_snake_case)]
-unsafe fn build_private_RSA(public_key: &[u8]) -> Result<LcPtr<*mut RSA>, KeyRejected> {
+pub(crate) unsafe fn build_private_RSA(public_key: &[u8]) -> Result<LcPtr<*mut RSA>, KeyRejected> {
     let mut cbs = cbs::build_CBS(public_key);
 
     let rsa =
@@ -530,10 +776,13 @@ fn verify_RSA(
     algorithm: &'static digest::Algorithm,
     padding: &'static RsaPadding,
     public_key: &LcPtr<*mut RSA>,
-    msg: &[u8],
+    digest: &digest::Digest,
     signature: &[u8],
     allowed_bit_size: &RangeInclusive<u32>,
 ) -> Result<(), Unspecified> {
+    if digest.algorithm() != algorithm {
+        return Err(Unspecified);
+    }
     unsafe {
         let n = ConstPointer::new(RSA_get0_n(**public_key))?;
         let n_bits = n.num_bits();
@@ -541,7 +790,6 @@ fn verify_RSA(
             return Err(Unspecified);
         }
 
-        let digest = digest::digest(algorithm, msg);
         let digest = digest.as_ref();
 
         let result = match padding {
@@ -637,7 +885,8 @@ where
     ) -> Result<(), Unspecified> {
         unsafe {
             let rsa = self.build_RSA()?;
-            verify_RSA(params.0, params.1, &rsa, message, signature, &params.2)
+            let digest = digest::digest(params.0, message);
+            verify_RSA(params.0, params.1, &rsa, &digest, signature, &params.2)
         }
     }
 }
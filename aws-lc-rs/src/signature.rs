@@ -237,7 +237,8 @@
 use crate::rsa;
 use rsa::{RSASigningAlgorithmId, RSAVerificationAlgorithmId, RsaSignatureEncoding};
 pub use rsa::{
-    RsaEncoding, RsaKeyPair, RsaParameters, RsaPublicKeyComponents, RsaSubjectPublicKey,
+    RsaEncoding, RsaKeyPair, RsaParameters, RsaPrivateKeyDer, RsaPublicKeyComponents,
+    RsaSubjectPublicKey,
 };
 
 use crate::{digest, ec, error, sealed, test};
@@ -245,10 +246,14 @@ use std::fmt::{Debug, Formatter};
 #[cfg(feature = "ring-sig-verify")]
 use untrusted::Input;
 
-pub use crate::ec::key_pair::EcdsaKeyPair;
+pub use crate::ec::key_pair::{EcdsaKeyPair, EcdsaPrivateKeyBytes};
 use crate::ec::EcdsaSignatureFormat;
+pub use crate::ec::PublicKey as EcdsaPublicKey;
 pub use crate::ec::{EcdsaSigningAlgorithm, EcdsaVerificationAlgorithm};
-pub use crate::ed25519::{Ed25519KeyPair, EdDSAParameters, ED25519_PUBLIC_KEY_LEN};
+pub use crate::ed25519::{
+    Ed25519KeyPair, Ed25519Seed, EdDSAParameters, PublicKey as Ed25519PublicKey,
+    ED25519_PUBLIC_KEY_LEN,
+};
 
 /// The longest signature is an ASN.1 P-384 signature where *r* and *s* are of
 /// maximum length with the leading high bit set on each. Then each component
@@ -322,6 +327,20 @@ pub trait VerificationAlgorithm: Debug + Sync + sealed::Sealed {
         msg: &[u8],
         signature: &[u8],
     ) -> Result<(), error::Unspecified>;
+
+    /// Verify the signature `signature` of the message digest `digest` with the
+    /// public key `public_key`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if inputs not verified, if the algorithm of `digest` is not
+    /// the digest algorithm of this verification algorithm, or if this verification
+    /// algorithm does not support pre-hashed messages.
+    fn verify_digest_sig(
+        &self,
+        public_key: &[u8],
+        digest: &digest::Digest,
+        signature: &[u8],
+    ) -> Result<(), error::Unspecified>;
 }
 
 /// An unparsed, possibly malformed, public key for signature verification.
@@ -365,6 +384,25 @@ impl<B: AsRef<[u8]>> UnparsedPublicKey<B> {
         self.algorithm
             .verify_sig(self.bytes.as_ref(), message, signature)
     }
+
+    /// Parses the public key and verifies `signature` is a valid signature of
+    /// the message whose digest is `digest`, using it. This is useful when the
+    /// message is too large to hold in memory and is digested incrementally with
+    /// a `digest::Context`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if inputs not verified, if the algorithm of `digest` is
+    /// not the digest algorithm of the verification algorithm, or if the verification
+    /// algorithm does not support pre-hashed messages (e.g. Ed25519).
+    #[inline]
+    pub fn verify_digest(
+        &self,
+        digest: &digest::Digest,
+        signature: &[u8],
+    ) -> Result<(), error::Unspecified> {
+        self.algorithm
+            .verify_digest_sig(self.bytes.as_ref(), digest, signature)
+    }
 }
 
 /// Verification of signatures using RSA keys of 1024-8192 bits, PKCS#1.5 padding, and SHA-1.
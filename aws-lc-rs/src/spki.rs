@@ -0,0 +1,29 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! X.509 `SubjectPublicKeyInfo` is specified in [RFC 5280 Section 4.1].
+//!
+//! [RFC 5280 Section 4.1]: https://tools.ietf.org/html/rfc5280#section-4.1
+
+use crate::test;
+use core::fmt;
+use std::fmt::{Debug, Formatter};
+
+/// A DER-encoded `SubjectPublicKeyInfo` document.
+#[derive(Clone)]
+pub struct Document {
+    pub(crate) bytes: Box<[u8]>,
+}
+
+impl AsRef<[u8]> for Document {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Debug for Document {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!("Document(\"{}\")", test::to_hex(&self.bytes)))
+    }
+}
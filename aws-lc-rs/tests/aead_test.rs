@@ -41,6 +41,86 @@ fn aead_aes_gcm_256() {
     );
 }
 
+#[test]
+fn aead_aes_gcm_siv_128() {
+    test_aead(
+        &aead::AES_128_GCM_SIV,
+        seal_with_key,
+        open_with_key,
+        test_file!("data/aead_aes_128_gcm_siv_tests.txt"),
+    );
+    test_aead(
+        &aead::AES_128_GCM_SIV,
+        seal_with_less_safe_key,
+        open_with_less_safe_key,
+        test_file!("data/aead_aes_128_gcm_siv_tests.txt"),
+    );
+}
+
+#[test]
+fn aead_aes_gcm_siv_256() {
+    test_aead(
+        &aead::AES_256_GCM_SIV,
+        seal_with_key,
+        open_with_key,
+        test_file!("data/aead_aes_256_gcm_siv_tests.txt"),
+    );
+    test_aead(
+        &aead::AES_256_GCM_SIV,
+        seal_with_less_safe_key,
+        open_with_less_safe_key,
+        test_file!("data/aead_aes_256_gcm_siv_tests.txt"),
+    );
+}
+
+#[test]
+fn aead_aes_ccm_128() {
+    test_aead(
+        &aead::AES_128_CCM,
+        seal_with_key,
+        open_with_key,
+        test_file!("data/aead_aes_128_ccm_tests.txt"),
+    );
+    test_aead(
+        &aead::AES_128_CCM,
+        seal_with_less_safe_key,
+        open_with_less_safe_key,
+        test_file!("data/aead_aes_128_ccm_tests.txt"),
+    );
+}
+
+#[test]
+fn aead_aes_ccm_128_8() {
+    test_aead(
+        &aead::AES_128_CCM_8,
+        seal_with_key,
+        open_with_key,
+        test_file!("data/aead_aes_128_ccm_8_tests.txt"),
+    );
+    test_aead(
+        &aead::AES_128_CCM_8,
+        seal_with_less_safe_key,
+        open_with_less_safe_key,
+        test_file!("data/aead_aes_128_ccm_8_tests.txt"),
+    );
+}
+
+#[test]
+fn aead_aes_ccm_128_4() {
+    test_aead(
+        &aead::AES_128_CCM_4,
+        seal_with_key,
+        open_with_key,
+        test_file!("data/aead_aes_128_ccm_4_tests.txt"),
+    );
+    test_aead(
+        &aead::AES_128_CCM_4,
+        seal_with_less_safe_key,
+        open_with_less_safe_key,
+        test_file!("data/aead_aes_128_ccm_4_tests.txt"),
+    );
+}
+
 #[test]
 fn aead_chacha20_poly1305() {
     test_aead(
@@ -57,6 +137,22 @@ fn aead_chacha20_poly1305() {
     );
 }
 
+#[test]
+fn aead_xchacha20_poly1305() {
+    test_aead(
+        &aead::XCHACHA20_POLY1305,
+        seal_with_key,
+        open_with_key,
+        test_file!("data/aead_xchacha20_poly1305_tests.txt"),
+    );
+    test_aead(
+        &aead::XCHACHA20_POLY1305,
+        seal_with_less_safe_key,
+        open_with_less_safe_key,
+        test_file!("data/aead_xchacha20_poly1305_tests.txt"),
+    );
+}
+
 #[allow(clippy::too_many_lines)]
 fn test_aead<Seal, Open>(
     aead_alg: &'static aead::Algorithm,
@@ -422,14 +518,359 @@ fn test_aead_nonce_sizes() {
 
     assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..nonce_len]).is_ok());
     assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..(nonce_len - 1)]).is_err());
-    assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..(nonce_len + 1)]).is_err());
+    assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..(nonce_len + 1)]).is_ok()); // 104 bits.
+    assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..(nonce_len + 2)]).is_err());
     assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..(nonce_len / 2)]).is_err());
-    assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..(nonce_len * 2)]).is_err());
+    assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..(nonce_len * 2)]).is_ok()); // 192 bits.
+    assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..(nonce_len * 2 - 1)]).is_err());
     assert!(aead::Nonce::try_assume_unique_for_key(&[]).is_err());
     assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..1]).is_err());
     assert!(aead::Nonce::try_assume_unique_for_key(&nonce[..16]).is_err()); // 128 bits.
 }
 
+// Test that a nonce is rejected when its length doesn't match the algorithm.
+#[test]
+fn test_aead_nonce_len_mismatch() {
+    for (algorithm, wrong_nonce_len) in [
+        (&aead::AES_128_GCM, aead::MAX_NONCE_LEN),
+        (&aead::AES_256_GCM_SIV, aead::MAX_NONCE_LEN),
+        (&aead::AES_128_CCM, aead::NONCE_LEN),
+        (&aead::AES_128_CCM_8, aead::MAX_NONCE_LEN),
+        (&aead::CHACHA20_POLY1305, aead::MAX_NONCE_LEN),
+        (&aead::CHACHA20_POLY1305, aead::CCM_NONCE_LEN),
+        (&aead::XCHACHA20_POLY1305, aead::NONCE_LEN),
+    ] {
+        assert_ne!(wrong_nonce_len, algorithm.nonce_len());
+        let key_bytes = vec![0u8; algorithm.key_len()];
+        let nonce_bytes = vec![0u8; wrong_nonce_len];
+        let make_nonce = || aead::Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap();
+
+        let mut in_out = vec![0u8; 32];
+        assert!(seal_with_key(
+            algorithm,
+            &key_bytes,
+            make_nonce(),
+            aead::Aad::from(&[][..]),
+            &mut in_out
+        )
+        .is_err());
+        assert!(seal_with_less_safe_key(
+            algorithm,
+            &key_bytes,
+            make_nonce(),
+            aead::Aad::from(&[][..]),
+            &mut in_out
+        )
+        .is_err());
+
+        let key = make_less_safe_key(algorithm, &key_bytes);
+        assert!(key
+            .seal_in_place_separate_tag(make_nonce(), aead::Aad::empty(), &mut in_out)
+            .is_err());
+        assert!(key
+            .open_in_place(make_nonce(), aead::Aad::empty(), &mut in_out)
+            .is_err());
+    }
+}
+
+#[test]
+fn test_aead_tag_len() {
+    for (algorithm, tag_len) in [
+        (&aead::AES_128_GCM, 16),
+        (&aead::AES_256_GCM_SIV, 16),
+        (&aead::AES_128_CCM, 16),
+        (&aead::AES_128_CCM_8, 8),
+        (&aead::AES_128_CCM_4, 4),
+        (&aead::XCHACHA20_POLY1305, 16),
+    ] {
+        assert_eq!(tag_len, algorithm.tag_len());
+        assert!(tag_len <= aead::MAX_TAG_LEN);
+
+        let key = make_less_safe_key(algorithm, &vec![7u8; algorithm.key_len()]);
+        let nonce_bytes = vec![3u8; algorithm.nonce_len()];
+        let plaintext = b"tag length test message";
+
+        let mut in_out = Vec::from(&plaintext[..]);
+        let tag = key
+            .seal_in_place_separate_tag(
+                aead::Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap(),
+                aead::Aad::empty(),
+                &mut in_out,
+            )
+            .unwrap();
+        assert_eq!(tag_len, tag.as_ref().len());
+
+        let mut combined = Vec::from(&plaintext[..]);
+        key.seal_in_place_append_tag(
+            aead::Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap(),
+            aead::Aad::empty(),
+            &mut combined,
+        )
+        .unwrap();
+        assert_eq!(plaintext.len() + tag_len, combined.len());
+        assert_eq!(&in_out[..], &combined[..plaintext.len()]);
+        assert_eq!(tag.as_ref(), &combined[plaintext.len()..]);
+
+        let opened = key
+            .open_in_place(
+                aead::Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap(),
+                aead::Aad::empty(),
+                &mut combined,
+            )
+            .unwrap();
+        assert_eq!(&plaintext[..], opened);
+    }
+}
+
+#[test]
+fn test_aead_ccm_short_tag_separate() {
+    for (algorithm, tag_len) in [(&aead::AES_128_CCM_8, 8), (&aead::AES_128_CCM_4, 4)] {
+        let key_bytes = vec![0x42u8; algorithm.key_len()];
+        let nonce_bytes = vec![0x24u8; algorithm.nonce_len()];
+        let plaintext = b"short tag separate seal";
+        let aad = b"short tag aad";
+
+        let mut combined = Vec::from(&plaintext[..]);
+        make_less_safe_key(algorithm, &key_bytes)
+            .seal_in_place_append_tag(
+                Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap(),
+                aead::Aad::from(aad),
+                &mut combined,
+            )
+            .unwrap();
+
+        // LessSafeKey
+        let mut in_out = Vec::from(&plaintext[..]);
+        let tag = make_less_safe_key(algorithm, &key_bytes)
+            .seal_in_place_separate_tag(
+                Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap(),
+                aead::Aad::from(aad),
+                &mut in_out,
+            )
+            .unwrap();
+        assert_eq!(tag_len, tag.as_ref().len());
+        assert_eq!(&combined[..plaintext.len()], &in_out[..]);
+        assert_eq!(&combined[plaintext.len()..], tag.as_ref());
+
+        // SealingKey
+        let mut in_out = Vec::from(&plaintext[..]);
+        let mut s_key: aead::SealingKey<OneNonceSequence> = make_key(
+            algorithm,
+            &key_bytes,
+            Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap(),
+        );
+        let tag = s_key
+            .seal_in_place_separate_tag(aead::Aad::from(aad), &mut in_out)
+            .unwrap();
+        assert_eq!(tag_len, tag.as_ref().len());
+        assert_eq!(&combined[..plaintext.len()], &in_out[..]);
+        assert_eq!(&combined[plaintext.len()..], tag.as_ref());
+    }
+}
+
+#[test]
+fn test_randomized_nonce_key() {
+    for algorithm in [&aead::AES_128_GCM, &aead::AES_256_GCM] {
+        let key_bytes = vec![0x42u8; algorithm.key_len()];
+        let key = aead::RandomizedNonceKey::new(algorithm, &key_bytes).unwrap();
+        let less_safe_key = make_less_safe_key(algorithm, &key_bytes);
+        let plaintext = b"randomized nonce plaintext";
+        let aad = b"randomized nonce aad";
+
+        // Append tag
+        let mut in_out = Vec::from(&plaintext[..]);
+        let nonce = key
+            .seal_in_place_append_tag(aead::Aad::from(aad), &mut in_out)
+            .unwrap();
+        assert_eq!(NONCE_LEN, nonce.as_ref().len());
+        assert_eq!(plaintext.len() + algorithm.tag_len(), in_out.len());
+
+        // The ciphertext is ordinary AES-GCM output for the returned nonce.
+        let mut interop = in_out.clone();
+        let opened = less_safe_key
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(nonce.as_ref()).unwrap(),
+                aead::Aad::from(aad),
+                &mut interop,
+            )
+            .unwrap();
+        assert_eq!(&plaintext[..], opened);
+
+        // Wrong nonce, wrong AAD and wrong nonce length are rejected.
+        let mut wrong_nonce = nonce.as_ref().to_vec();
+        wrong_nonce[0] ^= 1;
+        assert!(key
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(&wrong_nonce).unwrap(),
+                aead::Aad::from(aad),
+                &mut in_out.clone(),
+            )
+            .is_err());
+        assert!(key
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(nonce.as_ref()).unwrap(),
+                aead::Aad::empty(),
+                &mut in_out.clone(),
+            )
+            .is_err());
+        assert!(key
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(&[0u8; aead::MAX_NONCE_LEN]).unwrap(),
+                aead::Aad::from(aad),
+                &mut in_out.clone(),
+            )
+            .is_err());
+
+        let opened = key
+            .open_in_place(nonce, aead::Aad::from(aad), &mut in_out)
+            .unwrap();
+        assert_eq!(&plaintext[..], opened);
+
+        // Separate tag, opened with a shift.
+        let mut in_out = Vec::from(&plaintext[..]);
+        let (nonce, tag) = key
+            .seal_in_place_separate_tag(aead::Aad::from(aad), &mut in_out)
+            .unwrap();
+        assert_eq!(algorithm.tag_len(), tag.as_ref().len());
+        let mut shifted = vec![0u8; 13];
+        shifted.extend_from_slice(&in_out);
+        shifted.extend_from_slice(tag.as_ref());
+        let opened = key
+            .open_within(nonce, aead::Aad::from(aad), &mut shifted, 13..)
+            .unwrap();
+        assert_eq!(&plaintext[..], opened);
+    }
+}
+
+#[test]
+fn test_tls12_record_keys() {
+    use aead::nonce_sequence::Counter64Builder;
+
+    let salt = [0xA1, 0xB2, 0xC3, 0xD4];
+    for algorithm in [&aead::AES_128_GCM, &aead::AES_256_GCM] {
+        let key_bytes = vec![0x42u8; algorithm.key_len()];
+        let mut sealing_key = aead::TlsRecordSealingKey::new(
+            algorithm,
+            aead::TlsProtocolId::TLS12,
+            &key_bytes,
+            Counter64Builder::new().identifier(salt).counter(1).build(),
+        )
+        .unwrap();
+        let mut opening_key = aead::TlsRecordOpeningKey::new(
+            algorithm,
+            aead::TlsProtocolId::TLS12,
+            &key_bytes,
+            Counter64Builder::new().identifier(salt).counter(1).build(),
+        )
+        .unwrap();
+        let less_safe_key = make_less_safe_key(algorithm, &key_bytes);
+
+        for seq in 1u64..4 {
+            let mut in_out = Vec::from(&b"tls 1.2 record"[..]);
+            sealing_key
+                .seal_in_place_append_tag(aead::Aad::from(seq.to_be_bytes()), &mut in_out)
+                .unwrap();
+
+            // The nonce is the salt followed by the explicit counter.
+            let mut nonce = [0u8; NONCE_LEN];
+            nonce[..4].copy_from_slice(&salt);
+            nonce[4..].copy_from_slice(&seq.to_be_bytes());
+            let mut interop = in_out.clone();
+            less_safe_key
+                .open_in_place(
+                    Nonce::assume_unique_for_key(nonce),
+                    aead::Aad::from(seq.to_be_bytes()),
+                    &mut interop,
+                )
+                .unwrap();
+
+            let opened = opening_key
+                .open_in_place(aead::Aad::from(seq.to_be_bytes()), &mut in_out)
+                .unwrap();
+            assert_eq!(b"tls 1.2 record", opened);
+        }
+    }
+}
+
+#[test]
+fn test_tls13_record_keys() {
+    let iv = [
+        0x5d, 0x31, 0x3e, 0xb2, 0x67, 0x12, 0x76, 0xee, 0x13, 0x00, 0x0b, 0x30,
+    ];
+    for algorithm in [&aead::AES_128_GCM, &aead::AES_256_GCM] {
+        let key_bytes = vec![0x42u8; algorithm.key_len()];
+        let mut sealing_key = aead::TlsRecordSealingKey::new(
+            algorithm,
+            aead::TlsProtocolId::TLS13,
+            &key_bytes,
+            Tls13NonceSequence::new(iv, &[0, 1, 2]),
+        )
+        .unwrap();
+        let mut opening_key = aead::TlsRecordOpeningKey::new(
+            algorithm,
+            aead::TlsProtocolId::TLS13,
+            &key_bytes,
+            Tls13NonceSequence::new(iv, &[0, 1, 2]),
+        )
+        .unwrap();
+
+        for _ in 0..3 {
+            let mut in_out = Vec::from(&b"tls 1.3 record"[..]);
+            let tag = sealing_key
+                .seal_in_place_separate_tag(aead::Aad::empty(), &mut in_out)
+                .unwrap();
+            in_out.extend_from_slice(tag.as_ref());
+            let opened = opening_key
+                .open_in_place(aead::Aad::empty(), &mut in_out)
+                .unwrap();
+            assert_eq!(b"tls 1.3 record", opened);
+        }
+
+        // A record sequence number that goes backwards is rejected.
+        let mut sealing_key = aead::TlsRecordSealingKey::new(
+            algorithm,
+            aead::TlsProtocolId::TLS13,
+            &key_bytes,
+            Tls13NonceSequence::new(iv, &[0, 1, 5, 3]),
+        )
+        .unwrap();
+        for _ in 0..3 {
+            let _tag = sealing_key
+                .seal_in_place_separate_tag(aead::Aad::empty(), &mut [])
+                .unwrap();
+        }
+        assert!(sealing_key
+            .seal_in_place_separate_tag(aead::Aad::empty(), &mut [])
+            .is_err());
+    }
+}
+
+// Nonces as constructed in RFC 8446, Section 5.3, for the given record sequence numbers.
+struct Tls13NonceSequence {
+    iv: [u8; NONCE_LEN],
+    seqs: core::slice::Iter<'static, u64>,
+}
+
+impl Tls13NonceSequence {
+    fn new(iv: [u8; NONCE_LEN], seqs: &'static [u64]) -> Self {
+        Self {
+            iv,
+            seqs: seqs.iter(),
+        }
+    }
+}
+
+impl aead::NonceSequence for Tls13NonceSequence {
+    fn advance(&mut self) -> Result<Nonce, error::Unspecified> {
+        let seq = *self.seqs.next().ok_or(error::Unspecified)?;
+        let mut nonce = self.iv;
+        for (n, s) in nonce[4..].iter_mut().zip(seq.to_be_bytes()) {
+            *n ^= s;
+        }
+        Ok(Nonce::assume_unique_for_key(nonce))
+    }
+}
+
 #[allow(clippy::range_plus_one, clippy::cast_possible_truncation)]
 #[test]
 fn aead_chacha20_poly1305_openssh() {
@@ -503,6 +944,10 @@ fn test_aead_traits() {
     test::compile_time_assert_sync::<aead::UnboundKey>();
     test::compile_time_assert_send::<aead::LessSafeKey>();
     test::compile_time_assert_sync::<aead::LessSafeKey>();
+    test::compile_time_assert_send::<aead::RandomizedNonceKey>();
+    test::compile_time_assert_sync::<aead::RandomizedNonceKey>();
+    test::compile_time_assert_send::<aead::TlsRecordSealingKey<OneNonceSequence>>();
+    test::compile_time_assert_send::<aead::TlsRecordOpeningKey<OneNonceSequence>>();
 }
 
 #[test]
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use aws_lc_rs::cipher::{
-    DecryptingKey, EncryptingKey, EncryptionContext, OperatingMode, PaddedBlockDecryptingKey,
-    PaddedBlockEncryptingKey, UnboundCipherKey, AES_128, AES_256,
+    DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext, OperatingMode,
+    PaddedBlockDecryptingKey, PaddedBlockEncryptingKey, UnboundCipherKey, AES_128, AES_256,
+    AES_256_XTS, AES_BLOCK_LEN,
 };
 use aws_lc_rs::iv::FixedLength;
 use aws_lc_rs::test::from_hex;
@@ -74,6 +75,65 @@ macro_rules! cipher_kat {
     };
 }
 
+macro_rules! ecb_kat {
+    ($name:ident, $alg:expr, $key:literal, $plaintext:literal, $ciphertext:literal) => {
+        #[test]
+        fn $name() {
+            let key = from_hex($key).unwrap();
+            let input = from_hex($plaintext).unwrap();
+            let expected_ciphertext = from_hex($ciphertext).unwrap();
+
+            let unbound_key = UnboundCipherKey::new($alg, &key).unwrap();
+
+            let encrypting_key = EncryptingKey::ecb(unbound_key).unwrap();
+            assert_eq!(OperatingMode::ECB, encrypting_key.mode());
+            assert_eq!($alg, encrypting_key.algorithm());
+            let mut in_out = input.clone();
+            let context = encrypting_key.encrypt(&mut in_out).unwrap();
+            assert_eq!(expected_ciphertext.as_slice(), in_out);
+            assert_eq!("None", format!("{context:?}"));
+
+            let unbound_key2 = UnboundCipherKey::new($alg, &key).unwrap();
+            let decrypting_key = DecryptingKey::ecb(unbound_key2).unwrap();
+            assert_eq!(OperatingMode::ECB, decrypting_key.mode());
+            assert_eq!($alg, decrypting_key.algorithm());
+            let plaintext = decrypting_key.decrypt(&mut in_out, context).unwrap();
+            assert_eq!(input.as_slice(), plaintext);
+        }
+    };
+}
+
+macro_rules! xts_kat {
+    ($name:ident, $key:literal, $sector:literal, $plaintext:literal, $ciphertext:literal) => {
+        #[test]
+        fn $name() {
+            let key = from_hex($key).unwrap();
+            let input = from_hex($plaintext).unwrap();
+            let expected_ciphertext = from_hex($ciphertext).unwrap();
+
+            let unbound_key = UnboundCipherKey::new(&AES_256_XTS, &key).unwrap();
+
+            let encrypting_key = EncryptingKey::xts(unbound_key).unwrap();
+            assert_eq!(OperatingMode::XTS, encrypting_key.mode());
+            assert_eq!(&AES_256_XTS, encrypting_key.algorithm());
+            let mut in_out = input.clone();
+            encrypting_key
+                .less_safe_encrypt(&mut in_out, EncryptionContext::xts_sector($sector))
+                .unwrap();
+            assert_eq!(expected_ciphertext.as_slice(), in_out);
+
+            let unbound_key2 = UnboundCipherKey::new(&AES_256_XTS, &key).unwrap();
+            let decrypting_key = DecryptingKey::xts(unbound_key2).unwrap();
+            assert_eq!(OperatingMode::XTS, decrypting_key.mode());
+            assert_eq!(&AES_256_XTS, decrypting_key.algorithm());
+            let plaintext = decrypting_key
+                .decrypt(&mut in_out, DecryptionContext::xts_sector($sector))
+                .unwrap();
+            assert_eq!(input.as_slice(), plaintext);
+        }
+    };
+}
+
 macro_rules! padded_cipher_rt {
     ($name:ident, $alg:expr, $mode:expr, $constructor:ident, $key:literal, $plaintext:literal) => {
         #[test]
@@ -337,3 +397,342 @@ padded_cipher_rt!(
     "d4a8206dcae01242f9db79a4ecfe277d0f7bb8ccbafd8f9809adb39f35aa9b41",
     "a39c1fdf77ea3e1f18178c0ec237c70a34"
 );
+
+cipher_kat!(
+    test_kat_aes_128_cfb128_32_bytes,
+    &AES_128,
+    OperatingMode::CFB128,
+    cfb128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b"
+);
+
+cipher_kat!(
+    test_kat_aes_128_cfb128_21_bytes,
+    &AES_128,
+    OperatingMode::CFB128,
+    cfb128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e",
+    "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0"
+);
+
+cipher_kat!(
+    test_kat_aes_256_cfb128_32_bytes,
+    &AES_256,
+    OperatingMode::CFB128,
+    cfb128,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "dc7e84bfda79164b7ecd8486985d386039ffed143b28b1c832113c6331e5407b"
+);
+
+cipher_kat!(
+    test_kat_aes_256_cfb128_21_bytes,
+    &AES_256,
+    OperatingMode::CFB128,
+    cfb128,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e",
+    "dc7e84bfda79164b7ecd8486985d386039ffed143b"
+);
+
+cipher_kat!(
+    test_kat_aes_128_ofb_32_bytes,
+    &AES_128,
+    OperatingMode::OFB,
+    ofb,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed825"
+);
+
+cipher_kat!(
+    test_kat_aes_128_ofb_21_bytes,
+    &AES_128,
+    OperatingMode::OFB,
+    ofb,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e",
+    "3b3fd92eb72dad20333449f8e83cfb4a7789508d16"
+);
+
+cipher_kat!(
+    test_kat_aes_256_ofb_32_bytes,
+    &AES_256,
+    OperatingMode::OFB,
+    ofb,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "dc7e84bfda79164b7ecd8486985d38604febdc6740d20b3ac88f6ad82a4fb08d"
+);
+
+cipher_kat!(
+    test_kat_aes_256_ofb_21_bytes,
+    &AES_256,
+    OperatingMode::OFB,
+    ofb,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e",
+    "dc7e84bfda79164b7ecd8486985d38604febdc6740"
+);
+
+cipher_rt!(
+    test_rt_aes_128_cfb128_17_bytes,
+    &AES_128,
+    OperatingMode::CFB128,
+    cfb128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "6bc1bee22e409f96e93d7e117393172aae"
+);
+
+cipher_rt!(
+    test_rt_aes_256_cfb128_17_bytes,
+    &AES_256,
+    OperatingMode::CFB128,
+    cfb128,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "6bc1bee22e409f96e93d7e117393172aae"
+);
+
+cipher_rt!(
+    test_rt_aes_128_ofb_17_bytes,
+    &AES_128,
+    OperatingMode::OFB,
+    ofb,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "6bc1bee22e409f96e93d7e117393172aae"
+);
+
+cipher_rt!(
+    test_rt_aes_256_ofb_17_bytes,
+    &AES_256,
+    OperatingMode::OFB,
+    ofb,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "6bc1bee22e409f96e93d7e117393172aae"
+);
+
+cipher_kat!(
+    test_kat_aes_128_cbc_no_padding_32_bytes,
+    &AES_128,
+    OperatingMode::CBC,
+    cbc,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2"
+);
+
+cipher_kat!(
+    test_kat_aes_256_cbc_no_padding_32_bytes,
+    &AES_256,
+    OperatingMode::CBC,
+    cbc,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "f58c4c04d6e5f1ba779eabfb5f7bfbd69cfc4e967edb808d679f777bc6702c7d"
+);
+
+ecb_kat!(
+    test_kat_aes_128_ecb_32_bytes,
+    &AES_128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf"
+);
+
+ecb_kat!(
+    test_kat_aes_256_ecb_32_bytes,
+    &AES_256,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "f3eed1bdb5d2a03c064b5a7e3db181f8591ccb10d410ed26dc5ba74a31362870"
+);
+
+ecb_kat!(
+    test_kat_aes_128_ecb_0_bytes,
+    &AES_128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "",
+    ""
+);
+
+#[test]
+fn test_unpadded_block_modes_invalid_input() {
+    let key = from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+    let encrypting_keys = [
+        EncryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+        EncryptingKey::ecb(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+    ];
+    let decrypting_keys = [
+        DecryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+        DecryptingKey::ecb(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+    ];
+
+    // The input must be a multiple of the block length.
+    for len in [1, 15, 17, 31] {
+        let mut in_out = vec![0u8; len];
+        for key in &encrypting_keys {
+            assert!(key.encrypt(&mut in_out).is_err());
+        }
+    }
+
+    // ECB takes no IV, and CBC requires one.
+    let mut in_out = [0u8; 16];
+    let iv = || FixedLength::from([0u8; 16]);
+    assert!(encrypting_keys[1]
+        .less_safe_encrypt(&mut in_out, EncryptionContext::Iv128(iv()))
+        .is_err());
+    assert!(decrypting_keys[1]
+        .decrypt(&mut in_out, DecryptionContext::Iv128(iv()))
+        .is_err());
+    assert!(encrypting_keys[0]
+        .less_safe_encrypt(&mut in_out, EncryptionContext::None)
+        .is_err());
+    assert!(decrypting_keys[0]
+        .decrypt(&mut in_out, DecryptionContext::None)
+        .is_err());
+
+    // There are no IV bytes for an ECB context.
+    let context = DecryptionContext::None;
+    assert!(<&[u8]>::try_from(&context).is_err());
+}
+
+#[test]
+fn test_encrypt_block() {
+    // FIPS 197, Appendix C.
+    let mut block = [0u8; AES_BLOCK_LEN];
+    block.copy_from_slice(&from_hex("00112233445566778899aabbccddeeff").unwrap());
+
+    let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let key = UnboundCipherKey::new(&AES_128, &key).unwrap();
+    let mut encrypted = block;
+    key.encrypt_block(&mut encrypted).unwrap();
+    assert_eq!(
+        from_hex("69c4e0d86a7b0430d8cdb78070b4c55a")
+            .unwrap()
+            .as_slice(),
+        encrypted
+    );
+
+    let key = from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap();
+    let key = UnboundCipherKey::new(&AES_256, &key).unwrap();
+    let mut encrypted = block;
+    key.encrypt_block(&mut encrypted).unwrap();
+    assert_eq!(
+        from_hex("8ea2b7ca516745bfeafc49904b496089")
+            .unwrap()
+            .as_slice(),
+        encrypted
+    );
+
+    let mut key_bytes = [0x42u8; 64];
+    key_bytes[63] = 0x43;
+    let key = UnboundCipherKey::new(&AES_256_XTS, &key_bytes).unwrap();
+    assert!(key.encrypt_block(&mut block).is_err());
+}
+
+xts_kat!(
+    test_kat_aes_256_xts_16_bytes,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+    0,
+    "030a11181f262d343b424950575e656c",
+    "3f1f187c64e5cc3f3ee91069219b1238"
+);
+
+xts_kat!(
+    test_kat_aes_256_xts_17_bytes,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+    1,
+    "030a11181f262d343b424950575e656c73",
+    "a44d3df18eeb1fc6c560e81e535cfc32f4"
+);
+
+xts_kat!(
+    test_kat_aes_256_xts_31_bytes,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+    0x12_3456_789a,
+    "030a11181f262d343b424950575e656c737a81888f969da4abb2b9c0c7ced5",
+    "4b75e030f7ffcc8ea7344cf551b527c3876249c4e5b752185cc7bf73cf982a"
+);
+
+xts_kat!(
+    test_kat_aes_256_xts_32_bytes,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+    0x1_0000_0000_0000_0005,
+    "030a11181f262d343b424950575e656c737a81888f969da4abb2b9c0c7ced5dc",
+    "f32729f9f40b5f5deda64b44a4432b1e83de39976fef66af42f667459ed45d2a"
+);
+
+xts_kat!(
+    test_kat_aes_256_xts_64_bytes,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+    7,
+    "030a11181f262d343b424950575e656c737a81888f969da4abb2b9c0c7ced5dce3eaf1f8ff060d141b222930373e454c535a61686f767d848b9299a0a7aeb5bc",
+    "2a6921cf7bba2c099fe4eaa28d02a1ee4ecd1408db873d2458bd269f9df2984fe2cefa0d651fcdf2ca7d05f5f691b6e09e925f9ca51cc929a23cb8ee797e80fe"
+);
+
+#[test]
+fn test_aes_256_xts_invalid_keys() {
+    // The two halves of the key must differ.
+    assert!(UnboundCipherKey::new(&AES_256_XTS, &[0x42; 64]).is_err());
+    assert!(UnboundCipherKey::new(&AES_256_XTS, &[0x42; 32]).is_err());
+
+    let mut key_bytes = [0x42u8; 64];
+    key_bytes[63] = 0x43;
+
+    // XTS keys can only be used in XTS mode, and vice versa.
+    let key = || UnboundCipherKey::new(&AES_256_XTS, &key_bytes).unwrap();
+    assert!(EncryptingKey::ctr(key()).is_err());
+    assert!(DecryptingKey::ctr(key()).is_err());
+    assert!(PaddedBlockEncryptingKey::cbc_pkcs7(key()).is_err());
+    assert!(PaddedBlockDecryptingKey::cbc_pkcs7(key()).is_err());
+    let aes_key = || UnboundCipherKey::new(&AES_256, &key_bytes[..32]).unwrap();
+    assert!(EncryptingKey::xts(aes_key()).is_err());
+    assert!(DecryptingKey::xts(aes_key()).is_err());
+}
+
+#[test]
+fn test_aes_256_xts_invalid_input() {
+    let mut key_bytes = [0x42u8; 64];
+    key_bytes[63] = 0x43;
+    let encrypting_key =
+        EncryptingKey::xts(UnboundCipherKey::new(&AES_256_XTS, &key_bytes).unwrap()).unwrap();
+    let decrypting_key =
+        DecryptingKey::xts(UnboundCipherKey::new(&AES_256_XTS, &key_bytes).unwrap()).unwrap();
+
+    // Sectors must be at least one block long.
+    let mut in_out = [0u8; 15];
+    assert!(encrypting_key
+        .less_safe_encrypt(&mut in_out, EncryptionContext::xts_sector(0))
+        .is_err());
+    assert!(decrypting_key
+        .decrypt(&mut in_out, DecryptionContext::xts_sector(0))
+        .is_err());
+
+    // An IV is not a valid XTS context.
+    let mut in_out = [0u8; 16];
+    let iv = EncryptionContext::Iv128(FixedLength::from([0u8; 16]));
+    assert!(encrypting_key.less_safe_encrypt(&mut in_out, iv).is_err());
+    let iv = DecryptionContext::Iv128(FixedLength::from([0u8; 16]));
+    assert!(decrypting_key.decrypt(&mut in_out, iv).is_err());
+
+    // A random tweak is generated when none is provided.
+    let context = encrypting_key.encrypt(&mut in_out).unwrap();
+    assert_eq!("Tweak128", format!("{context:?}"));
+    assert_eq!(
+        [0u8; 16],
+        *decrypting_key.decrypt(&mut in_out, context).unwrap()
+    );
+}
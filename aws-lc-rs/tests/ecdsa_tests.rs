@@ -4,7 +4,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use aws_lc_rs::{
-    rand,
+    digest, rand,
     signature::{self, KeyPair},
     test, test_file,
 };
@@ -152,6 +152,37 @@ fn ecdsa_generate_pkcs8_test() {
     }
 }
 
+#[test]
+fn ecdsa_private_key_export_test() {
+    let rng = rand::SystemRandom::new();
+
+    for &(alg, private_key_len) in &[
+        (&signature::ECDSA_P256_SHA256_ASN1_SIGNING, 32),
+        (&signature::ECDSA_P384_SHA384_FIXED_SIGNING, 48),
+        (&signature::ECDSA_P521_SHA512_ASN1_SIGNING, 66),
+        (&signature::ECDSA_P256K1_SHA256_FIXED_SIGNING, 32),
+    ] {
+        let pkcs8 = signature::EcdsaKeyPair::generate_pkcs8(alg, &rng).unwrap();
+        let key_pair = signature::EcdsaKeyPair::from_pkcs8(alg, pkcs8.as_ref()).unwrap();
+
+        let exported = key_pair.to_pkcs8v1().unwrap();
+        assert_eq!(pkcs8.as_ref(), exported.as_ref());
+
+        let private_key = key_pair.private_key_bytes().unwrap();
+        assert_eq!(private_key_len, private_key.as_ref().len());
+        assert_eq!("EcdsaPrivateKeyBytes()", format!("{private_key:?}"));
+
+        let key_pair = signature::EcdsaKeyPair::from_private_key_and_public_key(
+            alg,
+            private_key.as_ref(),
+            key_pair.public_key().as_ref(),
+        )
+        .unwrap();
+        let exported = key_pair.to_pkcs8v1().unwrap();
+        assert_eq!(pkcs8.as_ref(), exported.as_ref());
+    }
+}
+
 #[test]
 fn signature_ecdsa_verify_asn1_test() {
     test_signature_ecdsa_verify_asn1(test_file!("data/ecdsa_verify_asn1_tests.txt"));
@@ -426,3 +457,57 @@ fn test_signature_ecdsa_sign_asn1(data_file: test::File) {
         Ok(())
     });
 }
+
+#[test]
+fn signature_ecdsa_sign_digest() {
+    const MESSAGE: &[u8] = b"a message that is digested in pieces";
+
+    let rng = rand::SystemRandom::new();
+
+    for (signing_alg, verification_alg, digest_alg) in [
+        (
+            &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+            &signature::ECDSA_P256_SHA256_ASN1,
+            &digest::SHA256,
+        ),
+        (
+            &signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+            &signature::ECDSA_P384_SHA384_FIXED,
+            &digest::SHA384,
+        ),
+        (
+            &signature::ECDSA_P521_SHA3_512_ASN1_SIGNING,
+            &signature::ECDSA_P521_SHA3_512_ASN1,
+            &digest::SHA3_512,
+        ),
+    ] {
+        let pkcs8 = signature::EcdsaKeyPair::generate_pkcs8(signing_alg, &rng).unwrap();
+        let key_pair = signature::EcdsaKeyPair::from_pkcs8(signing_alg, pkcs8.as_ref()).unwrap();
+        let public_key =
+            signature::UnparsedPublicKey::new(verification_alg, key_pair.public_key().as_ref());
+
+        let mut ctx = digest::Context::new(digest_alg);
+        for chunk in MESSAGE.chunks(5) {
+            ctx.update(chunk);
+        }
+        let digest = ctx.finish();
+
+        let signature = key_pair.sign_digest(&rng, &digest).unwrap();
+        assert!(public_key.verify(MESSAGE, signature.as_ref()).is_ok());
+        assert!(public_key
+            .verify_digest(&digest, signature.as_ref())
+            .is_ok());
+
+        let signature = key_pair.sign(&rng, MESSAGE).unwrap();
+        assert!(public_key
+            .verify_digest(&digest, signature.as_ref())
+            .is_ok());
+
+        // The digest algorithm must match the signing algorithm.
+        let other_digest = digest::digest(&digest::SHA512_256, MESSAGE);
+        assert!(key_pair.sign_digest(&rng, &other_digest).is_err());
+        assert!(public_key
+            .verify_digest(&other_digest, signature.as_ref())
+            .is_err());
+    }
+}
@@ -4,7 +4,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use aws_lc_rs::{
-    error,
+    digest, error,
     signature::{self, Ed25519KeyPair, KeyPair},
     test, test_file,
 };
@@ -112,6 +112,28 @@ fn test_ed25519_from_seed_and_public_key_misuse() {
     assert!(Ed25519KeyPair::from_seed_and_public_key(PUBLIC_KEY, PRIVATE_KEY).is_err());
 }
 
+#[test]
+fn test_ed25519_private_key_export() {
+    const PRIVATE_KEY: &[u8] = include_bytes!("data/ed25519_test_private_key.bin");
+    const PUBLIC_KEY: &[u8] = include_bytes!("data/ed25519_test_public_key.bin");
+
+    let key_pair = Ed25519KeyPair::from_seed_and_public_key(PRIVATE_KEY, PUBLIC_KEY).unwrap();
+
+    let seed = key_pair.seed();
+    assert_eq!(PRIVATE_KEY, seed.as_ref());
+    assert_eq!("Ed25519Seed()", format!("{seed:?}"));
+
+    let pkcs8v1 = key_pair.to_pkcs8v1().unwrap();
+    let pkcs8v2 = key_pair.to_pkcs8v2().unwrap();
+    assert!(pkcs8v1.as_ref().len() < pkcs8v2.as_ref().len());
+
+    for pkcs8 in [pkcs8v1, pkcs8v2] {
+        let parsed = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        assert_eq!(PUBLIC_KEY, parsed.public_key().as_ref());
+        assert_eq!(PRIVATE_KEY, parsed.seed().as_ref());
+    }
+}
+
 #[test]
 fn test_ed25519_from_pkcs8() {
     fn check_result(
@@ -204,3 +226,20 @@ fn ed25519_test_public_key_coverage() {
         format!("{key_pair:?}")
     );
 }
+
+#[test]
+fn test_ed25519_verify_digest_unsupported() {
+    const PRIVATE_KEY: &[u8] = include_bytes!("data/ed25519_test_private_key.p8");
+
+    let key_pair = Ed25519KeyPair::from_pkcs8(PRIVATE_KEY).unwrap();
+    let signature = key_pair.sign(b"message");
+    let public_key =
+        signature::UnparsedPublicKey::new(&signature::ED25519, key_pair.public_key().as_ref());
+    assert!(public_key.verify(b"message", signature.as_ref()).is_ok());
+
+    // Ed25519 signs the message itself, not a digest of it.
+    let digest = digest::digest(&digest::SHA512, b"message");
+    assert!(public_key
+        .verify_digest(&digest, signature.as_ref())
+        .is_err());
+}
@@ -0,0 +1,214 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::encryption::{
+    OaepAlgorithm, PrivateDecryptingKey, PublicEncryptingKey, RSA_OAEP_SHA1, RSA_OAEP_SHA256,
+    RSA_OAEP_SHA384, RSA_OAEP_SHA512,
+};
+use aws_lc_rs::signature::{KeyPair, RsaKeyPair};
+use aws_lc_rs::{test, test_file};
+
+const PRIVATE_KEY: &[u8] = include_bytes!("data/rsa_test_private_key_2048.p8");
+const PUBLIC_KEY: &[u8] = include_bytes!("data/rsa_test_public_key_2048.der");
+
+static OAEP_ALGORITHMS: &[&OaepAlgorithm] = &[
+    &RSA_OAEP_SHA1,
+    &RSA_OAEP_SHA256,
+    &RSA_OAEP_SHA384,
+    &RSA_OAEP_SHA512,
+];
+
+#[test]
+fn encryption_traits() {
+    test::compile_time_assert_send::<PublicEncryptingKey>();
+    test::compile_time_assert_sync::<PublicEncryptingKey>();
+    test::compile_time_assert_send::<PrivateDecryptingKey>();
+    test::compile_time_assert_sync::<PrivateDecryptingKey>();
+}
+
+#[test]
+fn encryption_decrypt_test() {
+    let private_key = PrivateDecryptingKey::from_pkcs8(PRIVATE_KEY).unwrap();
+
+    test::run(
+        test_file!("data/rsa_decrypt_tests.txt"),
+        |section, test_case| {
+            assert_eq!(section, "");
+
+            let digest_name = test_case.consume_string("Digest");
+            let label = test_case.consume_optional_bytes("Label");
+            let expected = test_case.consume_bytes("Plaintext");
+            let ciphertext = test_case.consume_bytes("Ciphertext");
+
+            let mut plaintext = vec![0u8; private_key.key_size_bytes()];
+            let actual = if digest_name == "PKCS1" {
+                private_key
+                    .decrypt_pkcs1_for_legacy_use_only(&ciphertext, &mut plaintext)
+                    .unwrap()
+            } else {
+                let alg = match digest_name.as_ref() {
+                    "SHA1" => &RSA_OAEP_SHA1,
+                    "SHA256" => &RSA_OAEP_SHA256,
+                    "SHA384" => &RSA_OAEP_SHA384,
+                    "SHA512" => &RSA_OAEP_SHA512,
+                    _ => panic!("Unsupported digest: {digest_name}"),
+                };
+                private_key
+                    .decrypt(alg, &ciphertext, &mut plaintext, label.as_deref())
+                    .unwrap()
+            };
+            assert_eq!(expected.as_slice(), actual);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn encryption_round_trip() {
+    let pkcs8 = RsaKeyPair::generate_pkcs8(2048).unwrap();
+    let private_key = PrivateDecryptingKey::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let public_key = private_key.public_key().unwrap();
+    assert_eq!(256, public_key.key_size_bytes());
+    assert_eq!(256, private_key.key_size_bytes());
+
+    for &alg in OAEP_ALGORITHMS {
+        let max_len = public_key.max_plaintext_size(alg);
+        let message = vec![0x5au8; max_len];
+
+        for label in [None, Some(&b""[..]), Some(&b"label"[..])] {
+            let mut ciphertext = vec![0u8; public_key.key_size_bytes()];
+            let ciphertext = public_key
+                .encrypt(alg, &message, &mut ciphertext, label)
+                .unwrap();
+            assert_eq!(256, ciphertext.len());
+
+            let mut plaintext = vec![0u8; private_key.key_size_bytes()];
+            let plaintext = private_key
+                .decrypt(alg, ciphertext, &mut plaintext, label)
+                .unwrap();
+            assert_eq!(message.as_slice(), plaintext);
+        }
+
+        // A plaintext one byte too long is rejected.
+        let message = vec![0x5au8; max_len + 1];
+        let mut ciphertext = vec![0u8; public_key.key_size_bytes()];
+        assert!(public_key
+            .encrypt(alg, &message, &mut ciphertext, None)
+            .is_err());
+    }
+}
+
+#[test]
+fn encryption_decrypt_failures() {
+    let private_key = PrivateDecryptingKey::from_pkcs8(PRIVATE_KEY).unwrap();
+    let public_key = PublicEncryptingKey::from_der(PUBLIC_KEY).unwrap();
+
+    let mut ciphertext = vec![0u8; public_key.key_size_bytes()];
+    let ciphertext = public_key
+        .encrypt(&RSA_OAEP_SHA256, b"secret", &mut ciphertext, Some(b"label"))
+        .unwrap()
+        .to_vec();
+
+    let mut plaintext = vec![0u8; private_key.key_size_bytes()];
+
+    // Wrong label.
+    assert!(private_key
+        .decrypt(&RSA_OAEP_SHA256, &ciphertext, &mut plaintext, Some(b"lab"))
+        .is_err());
+    assert!(private_key
+        .decrypt(&RSA_OAEP_SHA256, &ciphertext, &mut plaintext, None)
+        .is_err());
+
+    // Wrong digest.
+    assert!(private_key
+        .decrypt(
+            &RSA_OAEP_SHA384,
+            &ciphertext,
+            &mut plaintext,
+            Some(b"label")
+        )
+        .is_err());
+
+    // Wrong padding.
+    assert!(private_key
+        .decrypt_pkcs1_for_legacy_use_only(&ciphertext, &mut plaintext)
+        .is_err());
+
+    // Corrupted ciphertext.
+    let mut corrupted = ciphertext.clone();
+    corrupted[10] ^= 1;
+    assert!(private_key
+        .decrypt(&RSA_OAEP_SHA256, &corrupted, &mut plaintext, Some(b"label"))
+        .is_err());
+
+    // Output buffers that are too small.
+    let mut short = vec![0u8; private_key.key_size_bytes() - 1];
+    assert!(private_key
+        .decrypt(&RSA_OAEP_SHA256, &ciphertext, &mut short, Some(b"label"))
+        .is_err());
+    assert!(public_key
+        .encrypt(&RSA_OAEP_SHA256, b"secret", &mut short, None)
+        .is_err());
+
+    let plaintext = private_key
+        .decrypt(
+            &RSA_OAEP_SHA256,
+            &ciphertext,
+            &mut plaintext,
+            Some(b"label"),
+        )
+        .unwrap();
+    assert_eq!(b"secret", plaintext);
+}
+
+#[test]
+fn encryption_key_parsing() {
+    let key_pair = RsaKeyPair::from_pkcs8(PRIVATE_KEY).unwrap();
+    let public_key = PublicEncryptingKey::from_der(key_pair.public_key().as_ref()).unwrap();
+    assert_eq!(256, public_key.key_size_bytes());
+    assert_eq!(
+        256 - 2 * 32 - 2,
+        public_key.max_plaintext_size(&RSA_OAEP_SHA256)
+    );
+
+    assert!(PublicEncryptingKey::from_der(&PUBLIC_KEY[1..]).is_err());
+    assert!(PrivateDecryptingKey::from_pkcs8(PUBLIC_KEY).is_err());
+    assert!(PrivateDecryptingKey::from_der(PRIVATE_KEY).is_err());
+
+    let ec_pkcs8 = include_bytes!("data/ecdsa_test_private_key_p256.p8");
+    assert!(PrivateDecryptingKey::from_pkcs8(ec_pkcs8).is_err());
+
+    // Primes that are not a multiple of 512 bits are accepted for decryption,
+    // although `RsaKeyPair` rejects them for signing.
+    let pkcs8_2560 = include_bytes!("data/rsa_test_private_key_2560.p8");
+    assert!(RsaKeyPair::from_pkcs8(pkcs8_2560).is_err());
+    let private_key = PrivateDecryptingKey::from_pkcs8(pkcs8_2560).unwrap();
+    assert_eq!(320, private_key.key_size_bytes());
+    let public_key = private_key.public_key().unwrap();
+    let mut ciphertext = vec![0u8; public_key.key_size_bytes()];
+    let ciphertext = public_key
+        .encrypt(&RSA_OAEP_SHA256, b"secret", &mut ciphertext, None)
+        .unwrap();
+    let mut plaintext = vec![0u8; private_key.key_size_bytes()];
+    let plaintext = private_key
+        .decrypt(&RSA_OAEP_SHA256, ciphertext, &mut plaintext, None)
+        .unwrap();
+    assert_eq!(b"secret", plaintext);
+}
+
+#[test]
+fn encryption_debug() {
+    let private_key = PrivateDecryptingKey::from_pkcs8(PRIVATE_KEY).unwrap();
+    let public_key = private_key.public_key().unwrap();
+
+    assert_eq!("RSA_OAEP_SHA256", format!("{RSA_OAEP_SHA256:?}"));
+    assert_eq!(
+        "PrivateDecryptingKey { bits: 2048 }",
+        format!("{private_key:?}")
+    );
+    assert_eq!(
+        "PublicEncryptingKey { bits: 2048 }",
+        format!("{public_key:?}")
+    );
+}
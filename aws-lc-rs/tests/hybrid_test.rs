@@ -0,0 +1,91 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::error::Unspecified;
+use aws_lc_rs::hybrid::{self, PrivateKey, X25519_KYBER768_DRAFT00};
+use aws_lc_rs::test;
+
+#[test]
+fn test_hybrid_round_trip() {
+    let algorithm = &X25519_KYBER768_DRAFT00;
+    assert_eq!(1216, algorithm.public_key_share_len());
+    assert_eq!(1120, algorithm.ciphertext_share_len());
+    assert_eq!(64, algorithm.shared_secret_len());
+
+    let client_private_key = PrivateKey::generate(algorithm).unwrap();
+    assert_eq!(algorithm, client_private_key.algorithm());
+    let client_share = client_private_key.compute_public_key_share().unwrap();
+    assert_eq!(
+        algorithm.public_key_share_len(),
+        client_share.as_ref().len()
+    );
+
+    let (server_share, server_secret) =
+        hybrid::encapsulate(algorithm, client_share.as_ref()).unwrap();
+    assert_eq!(
+        algorithm.ciphertext_share_len(),
+        server_share.as_ref().len()
+    );
+    assert_eq!(algorithm.shared_secret_len(), server_secret.as_ref().len());
+
+    let client_secret = hybrid::agree_ephemeral(
+        client_private_key,
+        server_share.as_ref(),
+        Unspecified,
+        |secret| Ok(secret.to_vec()),
+    )
+    .unwrap();
+    assert_eq!(server_secret.as_ref(), &client_secret[..]);
+}
+
+#[test]
+fn test_hybrid_invalid_shares() {
+    let algorithm = &X25519_KYBER768_DRAFT00;
+    let client_private_key = PrivateKey::generate(algorithm).unwrap();
+    let client_share = client_private_key.compute_public_key_share().unwrap();
+
+    assert!(hybrid::encapsulate(algorithm, &client_share.as_ref()[1..]).is_err());
+    let mut too_long = client_share.as_ref().to_vec();
+    too_long.push(0);
+    assert!(hybrid::encapsulate(algorithm, &too_long).is_err());
+
+    // An X25519 public key that yields an all-zero shared secret is rejected.
+    let mut low_order = client_share.as_ref().to_vec();
+    low_order[..32].fill(0);
+    assert!(hybrid::encapsulate(algorithm, &low_order).is_err());
+
+    let (server_share, _) = hybrid::encapsulate(algorithm, client_share.as_ref()).unwrap();
+    let result: Result<(), &str> = hybrid::agree_ephemeral(
+        client_private_key,
+        &server_share.as_ref()[1..],
+        "error",
+        |_| unreachable!(),
+    );
+    assert_eq!(Err("error"), result);
+
+    let client_private_key = PrivateKey::generate(algorithm).unwrap();
+    let mut low_order = server_share.as_ref().to_vec();
+    low_order[..32].fill(0);
+    let result: Result<(), &str> =
+        hybrid::agree_ephemeral(client_private_key, &low_order, "error", |_| unreachable!());
+    assert_eq!(Err("error"), result);
+}
+
+#[test]
+fn test_hybrid_debug() {
+    let private_key = PrivateKey::generate(&X25519_KYBER768_DRAFT00).unwrap();
+    assert_eq!(
+        "PrivateKey { algorithm: X25519_KYBER768_DRAFT00 }",
+        format!("{private_key:?}")
+    );
+    assert_eq!(
+        "X25519_KYBER768_DRAFT00",
+        format!("{:?}", &X25519_KYBER768_DRAFT00)
+    );
+}
+
+#[test]
+fn test_hybrid_traits() {
+    test::compile_time_assert_send::<PrivateKey>();
+    test::compile_time_assert_sync::<PrivateKey>();
+}
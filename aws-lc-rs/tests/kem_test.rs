@@ -0,0 +1,135 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::error::Unspecified;
+use aws_lc_rs::kem::{
+    Algorithm, DecapsulationKey, EncapsulationKey, KYBER1024_R3, KYBER512_R3, KYBER768_R3,
+};
+use aws_lc_rs::test;
+
+const ALGORITHMS: [&Algorithm; 3] = [&KYBER512_R3, &KYBER768_R3, &KYBER1024_R3];
+
+#[test]
+fn test_kem_round_trip() {
+    for algorithm in ALGORITHMS {
+        let decapsulation_key = DecapsulationKey::generate(algorithm).unwrap();
+        assert_eq!(algorithm, decapsulation_key.algorithm());
+
+        let encapsulation_key = decapsulation_key.encapsulation_key().unwrap();
+        assert_eq!(algorithm, encapsulation_key.algorithm());
+
+        let (ciphertext, alice_secret) = encapsulation_key.encapsulate().unwrap();
+        assert_eq!(algorithm.ciphertext_len(), ciphertext.as_ref().len());
+        assert_eq!(algorithm.shared_secret_len(), alice_secret.as_ref().len());
+
+        let bob_secret = decapsulation_key
+            .decapsulate(ciphertext.as_ref(), Unspecified, |secret| {
+                Ok(secret.to_vec())
+            })
+            .unwrap();
+        assert_eq!(alice_secret.as_ref(), &bob_secret[..]);
+
+        // The key can be used for more than one decapsulation.
+        let (ciphertext, alice_secret) = encapsulation_key.encapsulate().unwrap();
+        decapsulation_key
+            .decapsulate(ciphertext.as_ref(), Unspecified, |secret| {
+                assert_eq!(alice_secret.as_ref(), secret);
+                Ok(())
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_kem_encapsulation_key_serialization() {
+    for algorithm in ALGORITHMS {
+        let decapsulation_key = DecapsulationKey::generate(algorithm).unwrap();
+        let key_bytes = decapsulation_key
+            .encapsulation_key()
+            .unwrap()
+            .key_bytes()
+            .unwrap();
+        assert_eq!(algorithm.encapsulation_key_len(), key_bytes.len());
+
+        let encapsulation_key = EncapsulationKey::new(algorithm, &key_bytes).unwrap();
+        assert_eq!(key_bytes, encapsulation_key.key_bytes().unwrap());
+
+        let (ciphertext, alice_secret) = encapsulation_key.encapsulate().unwrap();
+        decapsulation_key
+            .decapsulate(ciphertext.as_ref(), Unspecified, |secret| {
+                assert_eq!(alice_secret.as_ref(), secret);
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(
+            "TooSmall",
+            EncapsulationKey::new(algorithm, &key_bytes[1..])
+                .unwrap_err()
+                .description_()
+        );
+        let mut too_long = key_bytes.to_vec();
+        too_long.push(0);
+        assert_eq!(
+            "TooLarge",
+            EncapsulationKey::new(algorithm, &too_long)
+                .unwrap_err()
+                .description_()
+        );
+    }
+}
+
+#[test]
+fn test_kem_wrong_ciphertext() {
+    let decapsulation_key = DecapsulationKey::generate(&KYBER512_R3).unwrap();
+    let encapsulation_key = decapsulation_key.encapsulation_key().unwrap();
+    let (ciphertext, alice_secret) = encapsulation_key.encapsulate().unwrap();
+
+    // A ciphertext of the wrong length is rejected before the closure is called.
+    let result: Result<(), &str> =
+        decapsulation_key.decapsulate(&ciphertext.as_ref()[1..], "error", |_| unreachable!());
+    assert_eq!(Err("error"), result);
+
+    // A modified ciphertext yields a different shared secret.
+    let mut modified = ciphertext.as_ref().to_vec();
+    modified[0] ^= 1;
+    decapsulation_key
+        .decapsulate(&modified, Unspecified, |secret| {
+            assert_ne!(alice_secret.as_ref(), secret);
+            Ok(())
+        })
+        .unwrap();
+
+    // A ciphertext for another key yields a different shared secret.
+    let other_key = DecapsulationKey::generate(&KYBER512_R3).unwrap();
+    other_key
+        .decapsulate(ciphertext.as_ref(), Unspecified, |secret| {
+            assert_ne!(alice_secret.as_ref(), secret);
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_kem_debug() {
+    let decapsulation_key = DecapsulationKey::generate(&KYBER768_R3).unwrap();
+    assert_eq!(
+        "DecapsulationKey { algorithm: KYBER768_R3 }",
+        format!("{decapsulation_key:?}")
+    );
+    let encapsulation_key = decapsulation_key.encapsulation_key().unwrap();
+    assert_eq!(
+        "EncapsulationKey { algorithm: KYBER768_R3 }",
+        format!("{encapsulation_key:?}")
+    );
+    let (_, shared_secret) = encapsulation_key.encapsulate().unwrap();
+    assert_eq!("SharedSecret()", format!("{shared_secret:?}"));
+}
+
+#[test]
+fn test_kem_traits() {
+    test::compile_time_assert_send::<DecapsulationKey>();
+    test::compile_time_assert_sync::<DecapsulationKey>();
+    test::compile_time_assert_send::<EncapsulationKey>();
+    test::compile_time_assert_sync::<EncapsulationKey>();
+}
@@ -0,0 +1,100 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::key_wrap::{KeyEncryptionKey, AES_128, AES_256, KEY_WRAP_OVERHEAD};
+use aws_lc_rs::{test, test_file};
+
+fn kek(key: &[u8]) -> KeyEncryptionKey {
+    let algorithm = match key.len() {
+        16 => &AES_128,
+        32 => &AES_256,
+        _ => panic!("Unsupported KEK length: {}", key.len()),
+    };
+    KeyEncryptionKey::new(algorithm, key).unwrap()
+}
+
+#[test]
+fn key_wrap_traits() {
+    test::compile_time_assert_send::<KeyEncryptionKey>();
+    test::compile_time_assert_sync::<KeyEncryptionKey>();
+}
+
+#[test]
+fn key_wrap_test() {
+    test::run(
+        test_file!("data/key_wrap_tests.txt"),
+        |section, test_case| {
+            let kek = kek(&test_case.consume_bytes("KEK"));
+            let plaintext = test_case.consume_bytes("Plaintext");
+            let ciphertext = test_case.consume_bytes("Ciphertext");
+
+            let mut wrapped = vec![0u8; plaintext.len() + 2 * KEY_WRAP_OVERHEAD];
+            let mut unwrapped = vec![0u8; ciphertext.len()];
+            let (wrapped, unwrapped) = match section {
+                "KW" => (
+                    kek.wrap(&plaintext, &mut wrapped)?,
+                    kek.unwrap(&ciphertext, &mut unwrapped)?,
+                ),
+                "KWP" => (
+                    kek.wrap_with_padding(&plaintext, &mut wrapped)?,
+                    kek.unwrap_with_padding(&ciphertext, &mut unwrapped)?,
+                ),
+                _ => panic!("Unexpected section: {section}"),
+            };
+            assert_eq!(ciphertext.as_slice(), wrapped);
+            assert_eq!(plaintext.as_slice(), unwrapped);
+
+            // Any modification of the wrapped key is detected.
+            let mut tampered = ciphertext.clone();
+            tampered[ciphertext.len() - 1] ^= 1;
+            let mut unwrapped = vec![0u8; ciphertext.len()];
+            match section {
+                "KW" => assert!(kek.unwrap(&tampered, &mut unwrapped).is_err()),
+                _ => assert!(kek.unwrap_with_padding(&tampered, &mut unwrapped).is_err()),
+            }
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn key_wrap_invalid_lengths() {
+    let kek = kek(&[0x42; 32]);
+    let mut output = [0u8; 64];
+
+    // KW requires at least two 8-byte blocks of input.
+    assert!(kek.wrap(&[0u8; 8], &mut output).is_err());
+    assert!(kek.wrap(&[0u8; 17], &mut output).is_err());
+    assert!(kek.unwrap(&[0u8; 16], &mut output).is_err());
+    assert!(kek.unwrap(&[0u8; 25], &mut output).is_err());
+
+    // KWP rejects empty input.
+    assert!(kek.wrap_with_padding(&[], &mut output).is_err());
+
+    // Output buffers that are too small.
+    let key = [0x17u8; 32];
+    let mut short = [0u8; 32 + KEY_WRAP_OVERHEAD - 1];
+    assert!(kek.wrap(&key, &mut short).is_err());
+    assert!(kek.wrap_with_padding(&key, &mut short).is_err());
+
+    let mut wrapped = [0u8; 32 + KEY_WRAP_OVERHEAD];
+    kek.wrap(&key, &mut wrapped).unwrap();
+    let mut short = [0u8; 31];
+    assert!(kek.unwrap(&wrapped, &mut short).is_err());
+    assert!(kek.unwrap_with_padding(&wrapped, &mut short).is_err());
+
+    // A KEK of the wrong length.
+    assert!(KeyEncryptionKey::new(&AES_128, &[0x42; 32]).is_err());
+    assert!(KeyEncryptionKey::new(&AES_256, &[0x42; 16]).is_err());
+}
+
+#[test]
+fn key_wrap_debug() {
+    let kek = kek(&[0x42; 16]);
+    assert_eq!(&AES_128, kek.algorithm());
+    assert_eq!(
+        "KeyEncryptionKey { algorithm: Algorithm { id: Aes128, key_len: 16 }, .. }",
+        format!("{kek:?}")
+    );
+}
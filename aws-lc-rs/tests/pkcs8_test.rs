@@ -0,0 +1,156 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::pkcs8;
+use aws_lc_rs::rand::SystemRandom;
+use aws_lc_rs::signature::{self, EcdsaKeyPair, Ed25519KeyPair, KeyPair, RsaKeyPair};
+use std::num::NonZeroU32;
+
+const PASSWORD: &[u8] = b"correct horse battery staple";
+
+const RSA_PKCS8: &[u8] = include_bytes!("data/rsa_test_private_key_2048.p8");
+const RSA_ENCRYPTED: &[u8] = include_bytes!("data/rsa_test_private_key_2048_encrypted.p8");
+const ECDSA_PKCS8: &[u8] = include_bytes!("data/ecdsa_test_private_key_p256.p8");
+const ECDSA_ENCRYPTED: &[u8] = include_bytes!("data/ecdsa_test_private_key_p256_encrypted.p8");
+const ED25519_PKCS8: &[u8] = include_bytes!("data/ed25519_test_private_key.p8");
+const ED25519_ENCRYPTED: &[u8] = include_bytes!("data/ed25519_test_private_key_encrypted.p8");
+
+fn iterations() -> NonZeroU32 {
+    NonZeroU32::new(2048).unwrap()
+}
+
+fn decrypt_error(encrypted: &[u8], password: &[u8]) -> &'static str {
+    match pkcs8::decrypt(encrypted, password) {
+        Ok(_) => panic!("decryption unexpectedly succeeded"),
+        Err(err) => err.description_(),
+    }
+}
+
+#[test]
+fn pkcs8_decrypt_test() {
+    let decrypted = pkcs8::decrypt(RSA_ENCRYPTED, PASSWORD).unwrap();
+    assert_eq!(RSA_PKCS8, decrypted.as_ref());
+    let key_pair = RsaKeyPair::from_pkcs8(decrypted.as_ref()).unwrap();
+    let expected = RsaKeyPair::from_pkcs8(RSA_PKCS8).unwrap();
+    assert_eq!(
+        expected.public_key().as_ref(),
+        key_pair.public_key().as_ref()
+    );
+
+    let alg = &signature::ECDSA_P256_SHA256_ASN1_SIGNING;
+    let decrypted = pkcs8::decrypt(ECDSA_ENCRYPTED, PASSWORD).unwrap();
+    let key_pair = EcdsaKeyPair::from_pkcs8(alg, decrypted.as_ref()).unwrap();
+    let expected = EcdsaKeyPair::from_pkcs8(alg, ECDSA_PKCS8).unwrap();
+    assert_eq!(
+        expected.public_key().as_ref(),
+        key_pair.public_key().as_ref()
+    );
+
+    let decrypted = pkcs8::decrypt(ED25519_ENCRYPTED, PASSWORD).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(decrypted.as_ref()).unwrap();
+    let expected = Ed25519KeyPair::from_pkcs8(ED25519_PKCS8).unwrap();
+    assert_eq!(
+        expected.public_key().as_ref(),
+        key_pair.public_key().as_ref()
+    );
+}
+
+#[test]
+fn pkcs8_encrypt_round_trip() {
+    let rng = SystemRandom::new();
+
+    for document in [RSA_PKCS8, ECDSA_PKCS8, ED25519_PKCS8] {
+        let encrypted = pkcs8::encrypt(&rng, document, PASSWORD, iterations()).unwrap();
+        let decrypted = pkcs8::decrypt(encrypted.as_ref(), PASSWORD).unwrap();
+        assert_eq!(document, decrypted.as_ref());
+
+        // The salt and IV are random.
+        let other = pkcs8::encrypt(&rng, document, PASSWORD, iterations()).unwrap();
+        assert_ne!(encrypted.as_ref(), other.as_ref());
+    }
+
+    let key_pair = Ed25519KeyPair::from_pkcs8(ED25519_PKCS8).unwrap();
+    let document = key_pair.to_pkcs8v2().unwrap();
+    let encrypted = pkcs8::encrypt(&rng, document.as_ref(), b"", iterations()).unwrap();
+    let decrypted = pkcs8::decrypt(encrypted.as_ref(), b"").unwrap();
+    let parsed = Ed25519KeyPair::from_pkcs8(decrypted.as_ref()).unwrap();
+    assert_eq!(key_pair.public_key().as_ref(), parsed.public_key().as_ref());
+}
+
+#[test]
+fn pkcs8_encrypt_invalid_document() {
+    let rng = SystemRandom::new();
+    assert!(pkcs8::encrypt(&rng, &ECDSA_PKCS8[1..], PASSWORD, iterations()).is_err());
+    assert!(pkcs8::encrypt(&rng, ECDSA_ENCRYPTED, PASSWORD, iterations()).is_err());
+}
+
+#[test]
+fn pkcs8_decrypt_failures() {
+    // Wrong password.
+    for encrypted in [RSA_ENCRYPTED, ECDSA_ENCRYPTED, ED25519_ENCRYPTED] {
+        assert_eq!(
+            "InvalidEncoding",
+            decrypt_error(encrypted, b"incorrect horse battery staple")
+        );
+    }
+
+    // Malformed documents.
+    assert_eq!(
+        "InvalidEncoding",
+        decrypt_error(&ECDSA_ENCRYPTED[..ECDSA_ENCRYPTED.len() - 1], PASSWORD)
+    );
+    let mut trailing = ECDSA_ENCRYPTED.to_vec();
+    trailing.push(0);
+    assert_eq!("InvalidEncoding", decrypt_error(&trailing, PASSWORD));
+
+    // An unencrypted document.
+    assert_eq!("InvalidEncoding", decrypt_error(ECDSA_PKCS8, PASSWORD));
+
+    // A PKCS#12 scheme rather than PBES2.
+    assert_eq!(
+        "WrongAlgorithm",
+        decrypt_error(
+            include_bytes!("data/ecdsa_test_private_key_p256_pbe_sha1_3des.p8"),
+            b"password"
+        )
+    );
+
+    // An iteration count of `MAX_ITERATIONS + 1`.
+    assert_eq!(
+        "TooLarge",
+        decrypt_error(
+            include_bytes!("data/ecdsa_test_private_key_p256_too_many_iterations.p8"),
+            b"password"
+        )
+    );
+}
+
+#[test]
+fn pkcs8_decrypt_other_pbes2_schemes() {
+    // PBKDF2-HMAC-SHA1 with AES-256-CBC, and PBKDF2-HMAC-SHA256 with AES-128-CBC.
+    for encrypted in [
+        &include_bytes!("data/ecdsa_test_private_key_p256_pbkdf2_sha1.p8")[..],
+        &include_bytes!("data/ecdsa_test_private_key_p256_aes128.p8")[..],
+    ] {
+        let decrypted = pkcs8::decrypt(encrypted, b"password").unwrap();
+        assert_eq!(ECDSA_PKCS8, decrypted.as_ref());
+    }
+}
+
+#[test]
+fn pkcs8_encrypt_too_many_iterations() {
+    let rng = SystemRandom::new();
+    let iterations = NonZeroU32::new(pkcs8::MAX_ITERATIONS + 1).unwrap();
+    assert!(pkcs8::encrypt(&rng, ECDSA_PKCS8, PASSWORD, iterations).is_err());
+}
+
+#[test]
+fn pkcs8_document_with_wrong_algorithm() {
+    let decrypted = pkcs8::decrypt(RSA_ENCRYPTED, PASSWORD).unwrap();
+    assert_eq!(
+        "WrongAlgorithm",
+        Ed25519KeyPair::from_pkcs8(decrypted.as_ref())
+            .unwrap_err()
+            .description_()
+    );
+}
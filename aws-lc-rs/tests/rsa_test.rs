@@ -5,7 +5,7 @@
 
 use aws_lc_rs::signature::{KeyPair, RsaParameters, RsaSubjectPublicKey};
 use aws_lc_rs::test::to_hex_upper;
-use aws_lc_rs::{rand, signature, signature::RsaKeyPair, test, test_file};
+use aws_lc_rs::{digest, rand, signature, signature::RsaKeyPair, test, test_file};
 
 #[test]
 fn rsa_traits() {
@@ -267,3 +267,135 @@ fn rsa_test_public_key_coverage() {
         format!("{key_pair:?}")
     );
 }
+
+#[test]
+fn rsa_generate() {
+    const MESSAGE: &[u8] = b"hello, world";
+    let rng = rand::SystemRandom::new();
+
+    for bits in [2048, 3072, 4096] {
+        let key_pair = RsaKeyPair::generate(bits).unwrap();
+        assert_eq!(bits / 8, key_pair.public_modulus_len());
+
+        let mut signature = vec![0u8; key_pair.public_modulus_len()];
+        key_pair
+            .sign(&signature::RSA_PSS_SHA256, &rng, MESSAGE, &mut signature)
+            .unwrap();
+        signature::UnparsedPublicKey::new(
+            &signature::RSA_PSS_2048_8192_SHA256,
+            key_pair.public_key().as_ref(),
+        )
+        .verify(MESSAGE, &signature)
+        .unwrap();
+
+        let pkcs8 = RsaKeyPair::generate_pkcs8(bits).unwrap();
+        let key_pair = RsaKeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        assert_eq!(bits / 8, key_pair.public_modulus_len());
+    }
+
+    for bits in [0, 1024, 2047, 2049, 16384] {
+        assert!(RsaKeyPair::generate(bits).is_err());
+        assert!(RsaKeyPair::generate_pkcs8(bits).is_err());
+    }
+}
+
+#[test]
+fn rsa_private_key_export() {
+    const PRIVATE_KEY: &[u8] = include_bytes!("data/rsa_test_private_key_2048.p8");
+
+    let key_pair = RsaKeyPair::from_pkcs8(PRIVATE_KEY).unwrap();
+
+    let pkcs8 = key_pair.to_pkcs8v1().unwrap();
+    assert_eq!(PRIVATE_KEY, pkcs8.as_ref());
+
+    let der = key_pair.to_der().unwrap();
+    assert_eq!("RsaPrivateKeyDer()", format!("{der:?}"));
+    let parsed = RsaKeyPair::from_der(der.as_ref()).unwrap();
+    assert_eq!(key_pair.public_key().as_ref(), parsed.public_key().as_ref());
+    assert_eq!(PRIVATE_KEY, parsed.to_pkcs8v1().unwrap().as_ref());
+}
+
+#[test]
+fn rsa_sign_digest() {
+    const PRIVATE_KEY: &[u8] = include_bytes!("data/rsa_test_private_key_2048.p8");
+    const MESSAGE: &[u8] = b"a message that is digested in pieces";
+
+    let rng = rand::SystemRandom::new();
+    let key_pair = RsaKeyPair::from_pkcs8(PRIVATE_KEY).unwrap();
+    let public_key = key_pair.public_key().as_ref();
+
+    for (signing_alg, verification_alg, digest_alg) in [
+        (
+            &signature::RSA_PKCS1_SHA256,
+            &signature::RSA_PKCS1_2048_8192_SHA256,
+            &digest::SHA256,
+        ),
+        (
+            &signature::RSA_PKCS1_SHA512,
+            &signature::RSA_PKCS1_2048_8192_SHA512,
+            &digest::SHA512,
+        ),
+        (
+            &signature::RSA_PSS_SHA256,
+            &signature::RSA_PSS_2048_8192_SHA256,
+            &digest::SHA256,
+        ),
+        (
+            &signature::RSA_PSS_SHA384,
+            &signature::RSA_PSS_2048_8192_SHA384,
+            &digest::SHA384,
+        ),
+    ] {
+        let mut ctx = digest::Context::new(digest_alg);
+        for chunk in MESSAGE.chunks(5) {
+            ctx.update(chunk);
+        }
+        let digest = ctx.finish();
+
+        let mut signature = vec![0u8; key_pair.public_modulus_len()];
+        key_pair
+            .sign_digest(signing_alg, &rng, &digest, &mut signature)
+            .unwrap();
+
+        let public_key = signature::UnparsedPublicKey::new(verification_alg, public_key);
+        assert!(public_key.verify(MESSAGE, &signature).is_ok());
+        assert!(public_key.verify_digest(&digest, &signature).is_ok());
+
+        let mut message_signature = vec![0u8; key_pair.public_modulus_len()];
+        key_pair
+            .sign(signing_alg, &rng, MESSAGE, &mut message_signature)
+            .unwrap();
+        assert!(public_key
+            .verify_digest(&digest, &message_signature)
+            .is_ok());
+
+        // The digest algorithm must match the signing algorithm.
+        let other_digest = digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, MESSAGE);
+        assert!(key_pair
+            .sign_digest(signing_alg, &rng, &other_digest, &mut signature)
+            .is_err());
+        assert!(public_key.verify_digest(&other_digest, &signature).is_err());
+    }
+
+    // PKCS#1 v1.5 signatures are deterministic.
+    let digest = digest::digest(&digest::SHA256, MESSAGE);
+    let mut digest_signature = vec![0u8; key_pair.public_modulus_len()];
+    key_pair
+        .sign_digest(
+            &signature::RSA_PKCS1_SHA256,
+            &rng,
+            &digest,
+            &mut digest_signature,
+        )
+        .unwrap();
+    let mut message_signature = vec![0u8; key_pair.public_modulus_len()];
+    key_pair
+        .sign(
+            &signature::RSA_PKCS1_SHA256,
+            &rng,
+            MESSAGE,
+            &mut message_signature,
+        )
+        .unwrap();
+    assert_eq!(digest_signature, message_signature);
+}
@@ -0,0 +1,110 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::signature::{
+    EcdsaKeyPair, EcdsaPublicKey, Ed25519KeyPair, Ed25519PublicKey, KeyPair, RsaKeyPair,
+    RsaSubjectPublicKey,
+};
+use aws_lc_rs::{agreement, signature, test, test_file};
+
+#[test]
+fn spki_traits() {
+    test::compile_time_assert_send::<aws_lc_rs::spki::Document>();
+    test::compile_time_assert_sync::<aws_lc_rs::spki::Document>();
+}
+
+#[test]
+fn spki_round_trip_test() {
+    test::run(test_file!("data/spki_tests.txt"), |section, test_case| {
+        assert_eq!(section, "");
+
+        let algorithm = test_case.consume_string("Algorithm");
+        let spki = test_case.consume_bytes("SPKI");
+        let public_key = test_case.consume_bytes("PublicKey");
+
+        let ecdsa = EcdsaPublicKey::from_der(&spki);
+        let ed25519 = Ed25519PublicKey::from_der(&spki);
+        let rsa = RsaSubjectPublicKey::from_der(&spki);
+        let agreement = agreement::PublicKey::from_der(&spki);
+
+        let (is_ecdsa, is_ed25519, is_rsa, agreement_alg) = match algorithm.as_str() {
+            "P-256" => (true, false, false, Some(&agreement::ECDH_P256)),
+            "P-384" => (true, false, false, Some(&agreement::ECDH_P384)),
+            "P-521" => (true, false, false, Some(&agreement::ECDH_P521)),
+            "secp256k1" => (true, false, false, None),
+            "Ed25519" => (false, true, false, None),
+            "X25519" => (false, false, false, Some(&agreement::X25519)),
+            "RSA" => (false, false, true, None),
+            _ => panic!("Unsupported algorithm: {algorithm}"),
+        };
+
+        assert_eq!(is_ecdsa, ecdsa.is_ok());
+        if let Ok(key) = ecdsa {
+            assert_eq!(public_key.as_slice(), key.as_ref());
+            assert_eq!(spki.as_slice(), key.as_der()?.as_ref());
+        }
+
+        assert_eq!(is_ed25519, ed25519.is_ok());
+        if let Ok(key) = ed25519 {
+            assert_eq!(public_key.as_slice(), key.as_ref());
+            assert_eq!(spki.as_slice(), key.as_der()?.as_ref());
+        }
+
+        assert_eq!(is_rsa, rsa.is_ok());
+        if let Ok(key) = rsa {
+            assert_eq!(public_key.as_slice(), key.as_ref());
+            assert_eq!(spki.as_slice(), key.as_der()?.as_ref());
+        }
+
+        assert_eq!(agreement_alg.is_some(), agreement.is_ok());
+        if let Ok(key) = agreement {
+            assert_eq!(agreement_alg.unwrap(), key.algorithm());
+            assert_eq!(public_key.as_slice(), key.as_ref());
+            assert_eq!(spki.as_slice(), key.as_der()?.as_ref());
+        }
+
+        // Trailing data is rejected.
+        let mut trailing = spki.clone();
+        trailing.push(0);
+        assert!(EcdsaPublicKey::from_der(&trailing).is_err());
+        assert!(Ed25519PublicKey::from_der(&trailing).is_err());
+        assert!(RsaSubjectPublicKey::from_der(&trailing).is_err());
+        assert!(agreement::PublicKey::from_der(&trailing).is_err());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn spki_key_pair_public_keys() {
+    const ECDSA_PRIVATE_KEY: &[u8] = include_bytes!("data/ecdsa_test_private_key_p256.p8");
+    const ED25519_PRIVATE_KEY: &[u8] = include_bytes!("data/ed25519_test_private_key.p8");
+    const RSA_PRIVATE_KEY: &[u8] = include_bytes!("data/rsa_test_private_key_2048.p8");
+
+    let key_pair = EcdsaKeyPair::from_pkcs8(
+        &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+        ECDSA_PRIVATE_KEY,
+    )
+    .unwrap();
+    let spki = key_pair.public_key().as_der().unwrap();
+    let parsed = EcdsaPublicKey::from_der(spki.as_ref()).unwrap();
+    assert_eq!(key_pair.public_key().as_ref(), parsed.as_ref());
+
+    let key_pair = Ed25519KeyPair::from_pkcs8(ED25519_PRIVATE_KEY).unwrap();
+    let spki = key_pair.public_key().as_der().unwrap();
+    let parsed = Ed25519PublicKey::from_der(spki.as_ref()).unwrap();
+    assert_eq!(key_pair.public_key().as_ref(), parsed.as_ref());
+
+    let key_pair = RsaKeyPair::from_pkcs8(RSA_PRIVATE_KEY).unwrap();
+    let spki = key_pair.public_key().as_der().unwrap();
+    let parsed = RsaSubjectPublicKey::from_der(spki.as_ref()).unwrap();
+    assert_eq!(key_pair.public_key().as_ref(), parsed.as_ref());
+
+    let rng = aws_lc_rs::rand::SystemRandom::new();
+    let private_key = agreement::EphemeralPrivateKey::generate(&agreement::X25519, &rng).unwrap();
+    let public_key = private_key.compute_public_key().unwrap();
+    let spki = public_key.as_der().unwrap();
+    let parsed = agreement::PublicKey::from_der(spki.as_ref()).unwrap();
+    assert_eq!(public_key.as_ref(), parsed.as_ref());
+    assert_eq!(&agreement::X25519, parsed.algorithm());
+}